@@ -1,4 +1,5 @@
 use crate::SpriteData;
+use super::mask;
 
 #[derive(Clone, Copy, Debug)]
 pub enum Sprite {
@@ -8,19 +9,36 @@ pub enum Sprite {
 {{/sprites}}
 }
 
+/// The data and opacity mask for every sprite, in the order they are
+/// declared.  Kept in one static so each sprite's bytes are in the binary
+/// exactly once.
+static SPRITES: &[SpriteData] = &[
+{{#sprites}}
+    {
+        const DATA: &[u8] = &[ {{bytes}} ];
+        SpriteData {
+            width: {{width}},
+            height: {{height}},
+            flags: {{flags}},
+            data: DATA,
+            mask: &mask::<{{height}}>(DATA, {{width}}),
+        }
+    },
+{{/sprites}}
+];
+
 impl Sprite {
-    pub fn get(self) -> SpriteData {
-        use Sprite::*;
-        match self {
+    /// Every sprite, in the order they are declared.
+    pub const ALL: &[Sprite] = &[
 {{#sprites}}
-            {{name}} => SpriteData {
-                width: {{width}},
-                height: {{height}},
-                flags: {{flags}},
-                data: vec![ {{bytes}} ],
-            },
+        Sprite::{{name}},
 {{/sprites}}
-        }
+    ];
+
+    /// The data for the sprite, which lives in the binary so handing it out
+    /// never allocates.
+    pub fn get(self) -> &'static SpriteData {
+        &SPRITES[self as usize]
     }
 }
 
@@ -0,0 +1,207 @@
+//! Milestones that unlock once and stay unlocked.  A `Tracker` watches a run
+//! as it goes, and anything it unlocks is shown in a toast and kept on disk
+//! as a bit in a bitfield.
+
+use crate::platform::*;
+use crate::stats::Stats;
+use crate::{color, Cycle, EnemyKind};
+
+/// Frames each toast stays on screen.
+const TOAST_FRAMES: u32 = 150;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Achievement {
+    /// Shoot anything down.
+    FirstKill,
+    /// Live to see the first night.
+    Nightfall,
+    /// Make it to the fifth night.
+    NightFive,
+    /// Get through a whole day without losing any health.
+    Untouched,
+    /// Shoot down 50 turrets in one run.
+    TurretHunter,
+    /// Beat a boss.
+    BossSlayer,
+    /// Build a combo of 10 kills.
+    Combo,
+    /// Pick up 10 powerups in one run.
+    Collector,
+}
+
+impl Achievement {
+    pub const ALL: [Achievement; 8] = [
+        Achievement::FirstKill,
+        Achievement::Nightfall,
+        Achievement::NightFive,
+        Achievement::Untouched,
+        Achievement::TurretHunter,
+        Achievement::BossSlayer,
+        Achievement::Combo,
+        Achievement::Collector,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Achievement::FirstKill => "First kill",
+            Achievement::Nightfall => "Nightfall",
+            Achievement::NightFive => "Night 5",
+            Achievement::Untouched => "Untouched day",
+            Achievement::TurretHunter => "50 turrets",
+            Achievement::BossSlayer => "Boss slayer",
+            Achievement::Combo => "10 kill combo",
+            Achievement::Collector => "Collector",
+        }
+    }
+
+    /// The bit for this achievement in the saved bitfield.
+    pub fn bit(&self) -> u32 {
+        1 << *self as u32
+    }
+
+    pub fn unlocked(&self, bits: u32) -> bool {
+        bits & self.bit() != 0
+    }
+}
+
+/// Watches a run for achievements.
+#[derive(Clone, Debug)]
+pub struct Tracker {
+    /// Everything unlocked, on disk or this run.
+    unlocked: u32,
+    /// The cycle last time the run was watched.
+    cycle: Cycle,
+    /// Health lost by the start of the current day.
+    dawn_damage: u32,
+    /// Unlocked this run and waiting to be shown.
+    toasts: Vec<Achievement>,
+    toast_counter: u32,
+}
+
+impl Tracker {
+    /// A tracker for a run starting on the first day, with `unlocked`
+    /// already on disk.
+    pub fn new(unlocked: u32) -> Self {
+        Self {
+            unlocked,
+            cycle: Cycle::Day,
+            dawn_damage: 0,
+            toasts: Vec::new(),
+            toast_counter: 0,
+        }
+    }
+
+    /// Checks the run so far, returning the bits of anything newly unlocked.
+    pub fn watch(&mut self, cycle: Cycle, day: u32, stats: &Stats, best_combo: u32) -> u32 {
+        let before = self.unlocked;
+
+        if cycle != self.cycle {
+            match cycle {
+                Cycle::Night if stats.damage_taken == self.dawn_damage => self.unlock(Achievement::Untouched),
+                Cycle::Night => {}
+                Cycle::Day => self.dawn_damage = stats.damage_taken,
+            }
+            self.cycle = cycle;
+        }
+
+        if stats.total_kills() > 0 {
+            self.unlock(Achievement::FirstKill);
+        }
+        if cycle == Cycle::Night {
+            self.unlock(Achievement::Nightfall);
+            if day >= 5 {
+                self.unlock(Achievement::NightFive);
+            }
+        }
+        if stats.kills(EnemyKind::Turret) >= 50 {
+            self.unlock(Achievement::TurretHunter);
+        }
+        if stats.bosses > 0 {
+            self.unlock(Achievement::BossSlayer);
+        }
+        if best_combo >= 10 {
+            self.unlock(Achievement::Combo);
+        }
+        if stats.powerups >= 10 {
+            self.unlock(Achievement::Collector);
+        }
+
+        self.unlocked & !before
+    }
+
+    fn unlock(&mut self, achievement: Achievement) {
+        if !achievement.unlocked(self.unlocked) {
+            self.unlocked |= achievement.bit();
+            self.toasts.push(achievement);
+        }
+    }
+
+    /// Draws the oldest toast waiting, moving on to the next once it has
+    /// been up long enough.
+    pub fn draw_toast(&mut self) {
+        let Some(achievement) = self.toasts.first() else {
+            return;
+        };
+
+        color::set_draw(0x41);
+        rect(8, 34, 144, 24);
+        color::set_draw(0x02);
+        text("Achievement!", 32, 37);
+        color::set_draw(0x03);
+        let name = achievement.name();
+        text(name, 80 - 4 * name.len() as i32, 47);
+
+        self.toast_counter += 1;
+        if self.toast_counter >= TOAST_FRAMES {
+            self.toasts.remove(0);
+            self.toast_counter = 0;
+        }
+    }
+}
+
+/// Lists every achievement from height `y`, with the ones in `unlocked`
+/// marked.
+pub fn draw_list(unlocked: u32, y: i32) {
+    for (i, achievement) in Achievement::ALL.iter().enumerate() {
+        let row = y + i as i32 * 10;
+        if achievement.unlocked(unlocked) {
+            color::set_draw(0x02);
+            text("*", 10, row);
+        } else {
+            color::set_draw(0x03);
+        }
+        text(achievement.name(), 22, row);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unlocks_once() {
+        let mut tracker = Tracker::new(Achievement::Nightfall.bit());
+        let mut stats = Stats::default();
+        assert_eq!(tracker.watch(Cycle::Day, 1, &stats, 0), 0);
+
+        stats.add_kill(EnemyKind::Blaster);
+        assert_eq!(tracker.watch(Cycle::Day, 1, &stats, 10), Achievement::FirstKill.bit() | Achievement::Combo.bit());
+        assert_eq!(tracker.watch(Cycle::Day, 1, &stats, 10), 0);
+        // already on disk, so only the day counts
+        assert_eq!(tracker.watch(Cycle::Night, 1, &stats, 10), Achievement::Untouched.bit());
+        assert_eq!(tracker.toasts, [Achievement::FirstKill, Achievement::Combo, Achievement::Untouched]);
+    }
+
+    #[test]
+    fn untouched_only_counts_damage_since_dawn() {
+        let mut tracker = Tracker::new(0);
+        let mut stats = Stats::default();
+        stats.damage_taken = 1;
+        tracker.watch(Cycle::Day, 1, &stats, 0);
+        assert!(!Achievement::Untouched.unlocked(tracker.watch(Cycle::Night, 1, &stats, 0)));
+
+        stats.damage_taken = 2;
+        tracker.watch(Cycle::Day, 2, &stats, 0);
+        assert!(Achievement::Untouched.unlocked(tracker.watch(Cycle::Night, 2, &stats, 0)));
+    }
+}
@@ -0,0 +1,137 @@
+//! Screen shake and hit-stop.  Shaking moves everything drawn through
+//! `Render` by an offset that settles down over a few frames, and hit-stop
+//! freezes the game for a moment on big kills.  Neither touches the game
+//! itself, so replays play out the same with them on or off.
+
+use core::sync::atomic::{AtomicU32, AtomicU8, Ordering};
+
+static LEVEL: AtomicU8 = AtomicU8::new(Shake::Full as u8);
+
+/// Frames of shaking left.
+static SHAKE: AtomicU32 = AtomicU32::new(0);
+
+/// Frames of hit-stop left.
+static FREEZE: AtomicU32 = AtomicU32::new(0);
+
+/// Directions the screen is pushed in, one after another.
+const JOLTS: [(i32, i32); 8] = [(1, 0), (-1, 1), (0, -1), (1, 1), (-1, -1), (0, 1), (1, -1), (-1, 0)];
+
+/// How hard the screen shakes.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum Shake {
+    /// No shaking and no hit-stop.
+    Off,
+    Low,
+    #[default]
+    Full,
+}
+
+impl Shake {
+    pub const ALL: [Shake; 3] = [Shake::Off, Shake::Low, Shake::Full];
+
+    /// The setting after this one, wrapping around.
+    pub fn next(&self) -> Self {
+        Self::ALL[(*self as usize + 1) % Self::ALL.len()]
+    }
+
+    /// The setting saved as `b`, or the default for anything unknown.
+    pub fn from_byte(b: u8) -> Self {
+        Self::ALL.get(b as usize).copied().unwrap_or_default()
+    }
+
+    /// The most pixels the screen is ever pushed.
+    fn max_offset(&self) -> i32 {
+        match self {
+            Shake::Off => 0,
+            Shake::Low => 1,
+            Shake::Full => 3,
+        }
+    }
+}
+
+pub fn set_level(level: Shake) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+fn level() -> Shake {
+    Shake::from_byte(LEVEL.load(Ordering::Relaxed))
+}
+
+/// Shakes the screen for at least `frames` frames.
+pub fn shake(frames: u32) {
+    SHAKE.fetch_max(frames, Ordering::Relaxed);
+}
+
+/// Freezes the game for at least `frames` frames.
+pub fn freeze(frames: u32) {
+    if level() != Shake::Off {
+        FREEZE.fetch_max(frames, Ordering::Relaxed);
+    }
+}
+
+/// Checks if the game should sit out this frame, counting it off the
+/// hit-stop if so.
+pub fn frozen() -> bool {
+    FREEZE
+        .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |f| f.checked_sub(1))
+        .is_ok()
+}
+
+/// Runs every frame the game isn't frozen, settling the shake.
+pub fn update() {
+    let _ = SHAKE.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |s| s.checked_sub(1));
+}
+
+/// Stops any shaking or hit-stop.
+pub fn reset() {
+    SHAKE.store(0, Ordering::Relaxed);
+    FREEZE.store(0, Ordering::Relaxed);
+}
+
+/// How far to move everything drawn this frame.  The shake is strongest
+/// when it starts and eases off a pixel every four frames.
+pub fn offset() -> (i32, i32) {
+    let left = SHAKE.load(Ordering::Relaxed);
+    let strength = (left.div_ceil(4) as i32).min(level().max_offset());
+    let (dx, dy) = JOLTS[left as usize % JOLTS.len()];
+    (dx * strength, dy * strength)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settings_cycle_and_load() {
+        assert_eq!(Shake::Full.next(), Shake::Off);
+        assert_eq!(Shake::from_byte(Shake::Low as u8), Shake::Low);
+        assert_eq!(Shake::from_byte(9), Shake::default());
+    }
+
+    // the shake and hit-stop are shared by every test thread, so this is the
+    // only test that touches them
+    #[test]
+    fn shakes_settle_and_freezes_count_down() {
+        reset();
+        assert_eq!(offset(), (0, 0));
+
+        shake(12);
+        shake(4);
+        let strength = |(dx, dy): (i32, i32)| dx.abs().max(dy.abs());
+        assert_eq!(strength(offset()), Shake::Full.max_offset());
+        for _ in 0..8 {
+            update();
+        }
+        assert_eq!(strength(offset()), 1);
+        for _ in 0..4 {
+            update();
+        }
+        assert_eq!(offset(), (0, 0));
+
+        freeze(2);
+        assert!(frozen() && frozen() && !frozen());
+        freeze(5);
+        reset();
+        assert!(!frozen());
+    }
+}
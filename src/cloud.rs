@@ -1,4 +1,4 @@
-use crate::wasm4::sys::*;
+use crate::platform;
 use crate::util;
 
 fn adjusted_perlin(x: i32, y: i32, scale: f32) -> f32 {
@@ -8,9 +8,7 @@ fn adjusted_perlin(x: i32, y: i32, scale: f32) -> f32 {
 }
 
 pub fn draw(frame: u32, speed: f32) {
-    unsafe {
-        let buf = FRAMEBUFFER.as_mut().unwrap();
-
+    platform::framebuffer(|buf| {
         for x in 0..160_i32 {
             for y in 0..160_i32 {
                 let offset = (frame as f32 * speed) as i32;
@@ -28,5 +26,5 @@ pub fn draw(frame: u32, speed: f32) {
                 }
             }
         }
-    }
+    });
 }
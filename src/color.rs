@@ -1,43 +1,192 @@
-use crate::wasm4::sys::*;
+use crate::platform;
+use crate::util;
+use core::sync::atomic::{AtomicU8, Ordering};
 
-pub enum Palette {
-    Day,
-    Grey,
-    Night,
+static THEME: AtomicU8 = AtomicU8::new(0);
+
+/// A set of palettes for the whole game, one for each part of the cycle.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum Theme {
+    #[default]
+    Classic,
+    /// Greys with big steps between shades, and a black night.
+    Contrast,
+    /// Blues and oranges that stay apart with red-green colorblindness.
+    Colorsafe,
+    /// Shades of grey only, so no colors need telling apart.
+    Greyscale,
 }
 
-impl Palette {
-    fn bytes(&self) -> [u32; 4] {
+impl Theme {
+    pub const ALL: [Theme; 4] = [Theme::Classic, Theme::Contrast, Theme::Colorsafe, Theme::Greyscale];
+
+    /// The theme after this one, wrapping around.
+    pub fn next(&self) -> Self {
+        Self::ALL[(*self as usize + 1) % Self::ALL.len()]
+    }
+
+    /// The theme saved as `b`, or the default for anything unknown.
+    pub fn from_byte(b: u8) -> Self {
+        Self::ALL.get(b as usize).copied().unwrap_or_default()
+    }
+
+    fn bytes(&self, palette: &Palette) -> [u32; 4] {
         use Palette::*;
-        match self {
+        match (self, palette) {
             // Crimson: https://lospec.com/palette-list/crimson
-            Day => [ 0xeff9d6, 0xba5044, 0x7a1c4b, 0x1b0326 ],
-            
+            (Theme::Classic, Day) => [ 0xeff9d6, 0xba5044, 0x7a1c4b, 0x1b0326 ],
+            (Theme::Classic, Sunset) => [ 0xfcdcae, 0xe0703c, 0x8a2c4e, 0x2a0a2c ],
             // Grey Mist: https://lospec.com/palette-list/grey-mist
-            Grey => [ 0xf1ffe0, 0x988171, 0x463534, 0x1e1721 ],
-
+            (Theme::Classic, Transition) => [ 0xf1ffe0, 0x988171, 0x463534, 0x1e1721 ],
             // Some combination of colors I found or made.
-            Night => [ 0xd8d7f7, 0x435c6a, 0x252a45, 0x0f0f19 ],
+            (Theme::Classic, Night) => [ 0xd8d7f7, 0x435c6a, 0x252a45, 0x0f0f19 ],
+
+            (Theme::Contrast, Day) => [ 0xffffff, 0xb4b4b4, 0x5a5a5a, 0x000000 ],
+            (Theme::Contrast, Sunset) => [ 0xffeccc, 0xc48c60, 0x5c3a36, 0x000000 ],
+            (Theme::Contrast, Transition) => [ 0xe6e6e6, 0x9a9a9a, 0x4a4a4a, 0x000000 ],
+            (Theme::Contrast, Night) => [ 0xd0d8ff, 0x7484b8, 0x26304f, 0x000000 ],
+
+            // Built on the Okabe-Ito colors
+            (Theme::Colorsafe, Day) => [ 0xfff6d5, 0xe69f00, 0x0072b2, 0x1a1a2e ],
+            (Theme::Colorsafe, Sunset) => [ 0xffe2b4, 0xd55e00, 0x3c4f8a, 0x1a1424 ],
+            (Theme::Colorsafe, Transition) => [ 0xf0ece4, 0xb08a50, 0x35607a, 0x1c1c24 ],
+            (Theme::Colorsafe, Night) => [ 0xd6e9f8, 0x56b4e9, 0x1f4e79, 0x0b0b1a ],
+
+            (Theme::Greyscale, Day) => [ 0xf0f0f0, 0xa0a0a0, 0x585858, 0x101010 ],
+            (Theme::Greyscale, Sunset) => [ 0xe4e4e4, 0x989898, 0x525252, 0x0e0e0e ],
+            (Theme::Greyscale, Transition) => [ 0xd8d8d8, 0x909090, 0x4c4c4c, 0x0c0c0c ],
+            (Theme::Greyscale, Night) => [ 0xc0c0c8, 0x787880, 0x383840, 0x080808 ],
         }
     }
+}
+
+/// Switches the theme every palette is taken from.
+pub fn set_theme(theme: Theme) {
+    THEME.store(theme as u8, Ordering::Relaxed);
+}
+
+pub fn theme() -> Theme {
+    Theme::from_byte(THEME.load(Ordering::Relaxed))
+}
+
+/// A part of the cycle, colored by the current theme.
+pub enum Palette {
+    Day,
+    /// Warm colors on the way between day and night.
+    Sunset,
+    Transition,
+    Night,
+}
+
+/// Key palettes from day into night, and how far through the transition
+/// each is reached.
+pub const DUSK: &[(f32, Palette)] = &[
+    (0.0, Palette::Day),
+    (0.35, Palette::Sunset),
+    (0.5, Palette::Transition),
+    (1.0, Palette::Night),
+];
+
+/// Key palettes from night back into day.
+pub const DAWN: &[(f32, Palette)] = &[
+    (0.0, Palette::Night),
+    (0.5, Palette::Transition),
+    (0.75, Palette::Sunset),
+    (1.0, Palette::Day),
+];
+
+impl Palette {
+    fn bytes(&self) -> [u32; 4] {
+        theme().bytes(self)
+    }
 
     pub fn set(&self) {
-        let p = self.bytes();
-        unsafe {
-            *PALETTE = p;
+        platform::set_palette(self.bytes());
+    }
+}
+
+/// Sets the palette `w` of the way through `keys`, blending each color one
+/// channel at a time between the keys either side.
+pub fn blend(keys: &[(f32, Palette)], w: f32) {
+    let w = util::clamp(w, 0.0, 1.0);
+    let next = keys.iter().position(|(at, _)| *at >= w).unwrap_or(keys.len() - 1).max(1);
+    let ((start, from), (end, to)) = (&keys[next - 1], &keys[next]);
+    let t = util::clamp((w - start) / (end - start), 0.0, 1.0);
+
+    let (from, to) = (from.bytes(), to.bytes());
+    platform::set_palette(core::array::from_fn(|i| mix(from[i], to[i], t)));
+}
+
+/// The color `t` of the way from `from` to `to`, moving each channel in a
+/// straight line.
+fn mix(from: u32, to: u32, t: f32) -> u32 {
+    [0, 8, 16].iter().fold(0, |rgb, shift| {
+        let (a, b) = ((from >> shift & 0xff) as f32, (to >> shift & 0xff) as f32);
+        let channel = util::clamp((a + (b - a) * t).round(), 0.0, 255.0) as u32;
+        rgb | channel << shift
+    })
+}
+
+pub fn set_draw(v: u16) {
+    platform::set_draw_colors(v);
+}
+
+/// Darkens everything drawn so far by one shade.
+pub fn dim() {
+    platform::framebuffer(|buf| {
+        for byte in buf.iter_mut() {
+            let mut dimmed = 0;
+            for shift in (0..8).step_by(2) {
+                let color = (*byte >> shift) & 0b11;
+                dimmed |= (color + 1).min(3) << shift;
+            }
+            *byte = dimmed;
         }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn themes_cycle_through_every_one() {
+        let mut theme = Theme::default();
+        for expected in Theme::ALL.iter().cycle().skip(1).take(Theme::ALL.len()) {
+            theme = theme.next();
+            assert_eq!(theme, *expected);
+        }
+        assert_eq!(theme, Theme::default());
     }
 
-    pub fn transition_to(&self, idx: usize) {
-        let p = self.bytes();
-        unsafe {
-            PALETTE.as_mut().unwrap()[idx] = p[idx];
+    #[test]
+    fn unknown_themes_load_as_the_default() {
+        for theme in Theme::ALL {
+            assert_eq!(Theme::from_byte(theme as u8), theme);
         }
+        assert_eq!(Theme::from_byte(Theme::ALL.len() as u8), Theme::default());
+        assert_eq!(Theme::from_byte(u8::MAX), Theme::default());
     }
-}
 
-pub fn set_draw(v: u16) {
-    unsafe {
-        *DRAW_COLORS = v;
+    #[test]
+    fn mix_hits_both_ends() {
+        assert_eq!(mix(0x102030, 0xf0e0d0, 0.0), 0x102030);
+        assert_eq!(mix(0x102030, 0xf0e0d0, 1.0), 0xf0e0d0);
+        assert_eq!(mix(0x000000, 0xff00fe, 0.5), 0x80007f);
+    }
+
+    #[test]
+    fn mix_moves_each_channel_evenly() {
+        assert_eq!(mix(0x000000, 0xc8c8c8, 0.25), 0x323232);
+        assert_eq!(mix(0xc86400, 0x0064c8, 0.25), 0x966432);
+    }
+
+    #[test]
+    fn keys_cover_the_whole_transition() {
+        for keys in [DUSK, DAWN] {
+            assert_eq!(keys.first().map(|k| k.0), Some(0.0));
+            assert_eq!(keys.last().map(|k| k.0), Some(1.0));
+            assert!(keys.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        }
     }
 }
@@ -0,0 +1,96 @@
+//! Kills in quick succession build up a combo, which multiplies the points
+//! each kill is worth.  The combo falls away one kill at a time once the
+//! kills stop coming, and is lost entirely when the player takes a hit.
+
+/// Frames after a kill before the combo starts to fall.
+const WINDOW: u32 = 120;
+
+/// Frames between each kill falling off the combo once it does.
+const DECAY: u32 = 20;
+
+/// Kills in the combo for each step up in the multiplier.
+const STEP: u32 = 3;
+
+const MAX_MULTIPLIER: u32 = 8;
+
+#[derive(Clone, Debug, Default)]
+pub struct Combo {
+    /// Kills in the combo.
+    streak: u32,
+    /// Frames until the next kill falls off.
+    timer: u32,
+    /// Longest the combo has been this run.
+    best: u32,
+}
+
+impl Combo {
+    /// How many times over each kill is worth.
+    pub fn multiplier(&self) -> u32 {
+        (1 + self.streak / STEP).min(MAX_MULTIPLIER)
+    }
+
+    pub fn best(&self) -> u32 {
+        self.best
+    }
+
+    /// Adds a kill worth `points` to the combo, returning what it is worth
+    /// with the multiplier.
+    pub fn kill(&mut self, points: u32) -> u32 {
+        self.streak += 1;
+        self.best = self.best.max(self.streak);
+        self.timer = WINDOW;
+        points * self.multiplier()
+    }
+
+    /// Runs every frame, letting the combo fall once its time is up.
+    pub fn tick(&mut self) {
+        if self.timer > 0 {
+            self.timer -= 1;
+        } else if self.streak > 0 {
+            self.streak -= 1;
+            self.timer = DECAY;
+        }
+    }
+
+    /// Loses the whole combo.
+    pub fn reset(&mut self) {
+        self.streak = 0;
+        self.timer = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multiplier_steps_up_to_the_cap() {
+        let mut combo = Combo::default();
+        let worth: Vec<u32> = (0..30).map(|_| combo.kill(10)).collect();
+        assert_eq!(&worth[..7], &[10, 10, 20, 20, 20, 30, 30]);
+        assert_eq!(combo.multiplier(), MAX_MULTIPLIER);
+        assert_eq!(combo.best(), 30);
+    }
+
+    #[test]
+    fn falls_a_kill_at_a_time_after_the_window() {
+        let mut combo = Combo::default();
+        for _ in 0..STEP {
+            combo.kill(1);
+        }
+        assert_eq!(combo.multiplier(), 2);
+
+        for _ in 0..=WINDOW {
+            combo.tick();
+        }
+        assert_eq!(combo.multiplier(), 1);
+        for _ in 0..DECAY * 2 {
+            combo.tick();
+        }
+        assert_eq!(combo.multiplier(), 1);
+
+        combo.reset();
+        assert_eq!(combo.kill(1), 1);
+        assert_eq!(combo.best(), STEP);
+    }
+}
@@ -0,0 +1,195 @@
+//! How hard a run is.  Every number that makes the game easier or harder
+//! lives in a `Difficulty`, built from four knobs that are each set to a
+//! `Level`.  The presets set every knob to the same level, and the custom
+//! preset lets each be picked on its own.
+
+use crate::wave::Cooldown;
+
+/// How hard one knob is set.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum Level {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Level {
+    pub const ALL: [Level; 3] = [Level::Easy, Level::Normal, Level::Hard];
+
+    /// The level after this one, wrapping around.
+    pub fn next(&self) -> Self {
+        Self::ALL[(*self as usize + 1) % Self::ALL.len()]
+    }
+
+    /// The level saved as `b`, or the default for anything unknown.
+    pub fn from_byte(b: u8) -> Self {
+        Self::ALL.get(b as usize).copied().unwrap_or_default()
+    }
+}
+
+/// A difficulty to pick from the menu.  Each keeps its own high scores.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum Preset {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    Custom,
+}
+
+impl Preset {
+    pub const ALL: [Preset; 4] = [Preset::Easy, Preset::Normal, Preset::Hard, Preset::Custom];
+
+    /// The preset after this one, wrapping around.
+    pub fn next(&self) -> Self {
+        Self::ALL[(*self as usize + 1) % Self::ALL.len()]
+    }
+
+    /// The preset saved as `b`, or the default for anything unknown.
+    pub fn from_byte(b: u8) -> Self {
+        Self::ALL.get(b as usize).copied().unwrap_or_default()
+    }
+}
+
+/// The level of each knob for the custom preset.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct Custom {
+    /// Starting health.
+    pub health: Level,
+    /// How often enemies spawn and how long turrets stick around.
+    pub spawns: Level,
+    /// How often powerups drop.
+    pub drops: Level,
+    /// How long days and nights are.
+    pub cycle: Level,
+}
+
+impl Custom {
+    /// Every knob at `level`.
+    pub fn all(level: Level) -> Self {
+        Self {
+            health: level,
+            spawns: level,
+            drops: level,
+            cycle: level,
+        }
+    }
+
+    pub fn to_bytes(&self) -> [u8; 4] {
+        [self.health as u8, self.spawns as u8, self.drops as u8, self.cycle as u8]
+    }
+
+    pub fn from_bytes(b: [u8; 4]) -> Self {
+        Self {
+            health: Level::from_byte(b[0]),
+            spawns: Level::from_byte(b[1]),
+            drops: Level::from_byte(b[2]),
+            cycle: Level::from_byte(b[3]),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Difficulty {
+    /// Which high score table runs go in.
+    pub preset: Preset,
+    /// Hearts each player starts with.
+    pub starting_health: u32,
+    /// Frames between random spawns.
+    pub cooldown: Cooldown,
+    /// Frames a turret waits at its target height before leaving.
+    pub still_cap: u32,
+    /// Scales every enemy's chance of dropping a powerup.
+    pub drop_scale: f32,
+    /// Taken off the drop chance for each heart the neediest player has.
+    pub drop_per_health: f32,
+    /// Frames in a full day and night.
+    pub cycle_length: u32,
+    /// How much of the cycle is day, as a numerator and denominator.
+    pub day_part: (u32, u32),
+}
+
+impl Default for Difficulty {
+    fn default() -> Self {
+        Self::new(Preset::Normal, Custom::default())
+    }
+}
+
+impl Difficulty {
+    /// The difficulty for `preset`, using the knobs in `custom` if it is the
+    /// custom preset.
+    pub fn new(preset: Preset, custom: Custom) -> Self {
+        let knobs = match preset {
+            Preset::Easy => Custom::all(Level::Easy),
+            Preset::Normal => Custom::all(Level::Normal),
+            Preset::Hard => Custom::all(Level::Hard),
+            Preset::Custom => custom,
+        };
+
+        let starting_health = match knobs.health {
+            Level::Easy => 5,
+            Level::Normal => 3,
+            Level::Hard => 2,
+        };
+        let (cooldown, still_cap) = match knobs.spawns {
+            Level::Easy => (Cooldown { start: 130, step: 300, min: 45, night: (3, 4) }, 240),
+            Level::Normal => (Cooldown { start: 100, step: 300, min: 30, night: (3, 4) }, 300),
+            Level::Hard => (Cooldown { start: 80, step: 240, min: 20, night: (2, 3) }, 420),
+        };
+        let (drop_scale, drop_per_health) = match knobs.drops {
+            Level::Easy => (1.5, 0.03),
+            Level::Normal => (1.0, 0.03),
+            Level::Hard => (0.7, 0.04),
+        };
+        let (cycle_length, day_part) = match knobs.cycle {
+            Level::Easy => (4200, (3, 4)),
+            Level::Normal => (3600, (3, 4)),
+            Level::Hard => (3000, (2, 3)),
+        };
+
+        Self {
+            preset,
+            starting_health,
+            cooldown,
+            still_cap,
+            drop_scale,
+            drop_per_health,
+            cycle_length,
+            day_part,
+        }
+    }
+
+    /// Frames into the cycle that night starts.
+    pub fn night_start(&self) -> u32 {
+        self.cycle_length / self.day_part.1 * self.day_part.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_ignore_the_custom_knobs() {
+        let custom = Custom { health: Level::Easy, spawns: Level::Hard, drops: Level::Easy, cycle: Level::Hard };
+        assert_eq!(Difficulty::new(Preset::Normal, custom), Difficulty::default());
+        assert_eq!(Difficulty::new(Preset::Custom, custom).starting_health, 5);
+        assert_eq!(Difficulty::new(Preset::Custom, custom).cycle_length, 3000);
+    }
+
+    #[test]
+    fn night_starts_part_way_through_the_cycle() {
+        assert_eq!(Difficulty::default().night_start(), 2700);
+        assert_eq!(Difficulty::new(Preset::Hard, Custom::default()).night_start(), 2000);
+    }
+
+    #[test]
+    fn knobs_round_trip() {
+        let custom = Custom { health: Level::Hard, spawns: Level::Easy, drops: Level::Normal, cycle: Level::Easy };
+        assert_eq!(Custom::from_bytes(custom.to_bytes()), custom);
+        assert_eq!(Custom::from_bytes([9, 9, 9, 9]), Custom::default());
+        assert_eq!(Preset::from_byte(Preset::Custom as u8), Preset::Custom);
+        assert_eq!(Preset::Custom.next(), Preset::Easy);
+    }
+}
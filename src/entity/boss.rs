@@ -0,0 +1,269 @@
+use super::*;
+use crate::sound;
+use crate::platform::rect;
+
+/// Health on the first night, with more every night after.
+const BASE_HEALTH: u32 = 40;
+const HEALTH_PER_DAY: u32 = 10;
+
+/// Height the boss flies down to before it starts fighting.
+const HOVER_HEIGHT: f32 = 30.0;
+
+/// Frames spent blowing up.
+const DEATH_FRAMES: u32 = 120;
+
+/// Where explosions go off during the death sequence, from the center.
+const BLASTS: [(i32, i32); 6] = [(-8, -2), (6, 3), (-12, 4), (10, -3), (0, 1), (-3, 6)];
+
+/// Which attack the boss uses, moving on as it loses health.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Phase {
+    /// Fans of bullets while sweeping side to side.
+    Sweep,
+    /// Curling arms of bullets, turning every shot.
+    Spiral,
+    /// Rotating rings and staggered cannon fire, sweeping faster.
+    Frenzy,
+}
+
+/// The big ship that shows up at the end of every night.
+#[derive(Clone, Debug)]
+pub struct Boss {
+    pos: (f32, f32),
+    vel: (f32, f32),
+    health: u32,
+    max_health: u32,
+    fire_counter: u32,
+
+    time_alive: u32,
+    dying_counter: u32,
+}
+
+impl Boss {
+    pub fn new(day: u32) -> Self {
+        let health = BASE_HEALTH + HEALTH_PER_DAY * day.saturating_sub(1);
+        Self {
+            pos: (80.0, -10.0),
+            vel: (0.0, 0.5),
+            health,
+            max_health: health,
+            fire_counter: 0,
+
+            time_alive: 0,
+            dying_counter: 0,
+        }
+    }
+
+    pub fn phase(&self) -> Phase {
+        match self.health * 3 / self.max_health {
+            2.. => Phase::Sweep,
+            1 => Phase::Spiral,
+            _ => Phase::Frenzy,
+        }
+    }
+
+    /// Still flying down into place.
+    fn entering(&self) -> bool {
+        self.pos.1 < HOVER_HEIGHT
+    }
+
+    /// How far it sways either side of the middle, and how fast.
+    fn sway(&self) -> (f32, f32) {
+        match self.phase() {
+            Phase::Sweep => (40.0, 0.02),
+            Phase::Spiral => (20.0, 0.015),
+            Phase::Frenzy => (50.0, 0.04),
+        }
+    }
+
+    /// Draws how much health is left along the top of the screen.
+    pub fn draw_health_bar(&self) {
+        color::set_draw(0x40);
+        rect(30, 22, 100, 5);
+        color::set_draw(0x03);
+        let width = 98 * self.health / self.max_health;
+        rect(31, 23, width, 3);
+    }
+}
+
+impl Alive for Boss {
+    fn dead(&self) -> bool {
+        self.dying_counter > DEATH_FRAMES
+    }
+
+    fn dying(&self) -> bool {
+        self.dying_counter > 0
+    }
+
+    fn health(&self) -> u32 {
+        self.health
+    }
+
+    fn health_mut(&mut self) -> &mut u32 {
+        &mut self.health
+    }
+
+    fn kill(&mut self) {
+        if !self.dying() {
+            sound::enemy_death();
+            self.health = 0;
+            self.dying_counter = 1;
+        }
+    }
+}
+
+impl Shoot for Boss {
+    fn shoot(&mut self) -> Vec<Bullet> {
+        if self.dying() || self.entering() {
+            return vec![];
+        }
+
+        let t = self.fire_counter;
+        let origin = (self.pos.0, self.bottom() as f32);
+        let bullets = match self.phase() {
+            Phase::Sweep if t.is_multiple_of(45) => {
+                Pattern::Fan { spread: 1.2 }.fire(origin, 5, 1.2, 0.0)
+            }
+            Phase::Spiral if t.is_multiple_of(20) => {
+                Pattern::Spiral { twist: 0.25 }.fire(origin, 4, 1.2, t as f32 * 0.12)
+            }
+            Phase::Frenzy if t.is_multiple_of(50) => {
+                Pattern::Ring { radius: 8.0 }.fire(origin, 12, 1.0, t as f32 * 0.1)
+            }
+            Phase::Frenzy if t.is_multiple_of(20) => {
+                Pattern::Staggered { gap: 10.5, stagger: true }.fire(origin, 3, 2.0, 0.0)
+            }
+            _ => vec![],
+        };
+
+        if !bullets.is_empty() {
+            sound::enemy_fire();
+        }
+        bullets
+    }
+}
+
+impl Render for Boss {
+    fn pos(&self) -> (f32, f32) { self.pos }
+
+    fn sprite(&self) -> Sprite {
+        if (self.time_alive / 15).is_multiple_of(2) {
+            Sprite::boss1
+        } else {
+            Sprite::boss2
+        }
+    }
+
+    fn draw(&self) {
+        color::set_draw(0x4320);
+        if !self.dying() {
+            let (x, y) = self.screen_pos();
+            self.sprite().get().draw(x, y);
+            return;
+        }
+
+        // flicker with explosions going off across the hull
+        if (self.dying_counter / 4).is_multiple_of(2) {
+            let (x, y) = self.screen_pos();
+            self.sprite().get().draw(x, y);
+        }
+        let blast = [Sprite::enemy2, Sprite::enemy3, Sprite::enemy4][(self.dying_counter / 5 % 3) as usize];
+        let (dx, dy) = BLASTS[(self.dying_counter / 15) as usize % BLASTS.len()];
+        let blast = blast.get();
+        let (shake_x, shake_y) = camera::offset();
+        blast.draw(
+            self.pos.0 as i32 + dx + shake_x - blast.width as i32 / 2,
+            self.pos.1 as i32 + dy + shake_y - blast.height as i32 / 2,
+        );
+    }
+}
+
+impl Movement for Boss {
+    fn pos_mut(&mut self) -> &mut (f32, f32) { &mut self.pos }
+    fn vel(&self) -> (f32, f32) { self.vel }
+    fn vel_mut(&mut self) -> &mut (f32, f32) { &mut self.vel }
+
+    fn update(&mut self, _: u32) {
+        if self.dying() {
+            self.dying_counter += 1;
+            if self.dying_counter.is_multiple_of(15) {
+                sound::enemy_death();
+            }
+            self.vel = (0.0, 0.2);
+        } else if self.entering() {
+            self.vel = (0.0, 0.5);
+        } else {
+            // chase a point swaying about the middle, so changing phase
+            // never makes it jump
+            let (width, speed) = self.sway();
+            let target = 80.0 + width * (self.fire_counter as f32 * speed).sin();
+            self.vel = (util::clamp(target - self.pos.0, -1.5, 1.5), 0.0);
+            self.fire_counter += 1;
+        }
+        self.time_alive += 1;
+        self.advance();
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+
+    /// A boss already flown down into place.
+    fn hovering(day: u32) -> Boss {
+        let mut boss = Boss::new(day);
+        while boss.entering() {
+            boss.update(0);
+        }
+        boss
+    }
+
+    #[test]
+    fn tougher_every_night() {
+        assert_eq!(Boss::new(1).health(), 40);
+        assert_eq!(Boss::new(3).health(), 60);
+    }
+
+    #[test]
+    fn holds_fire_until_in_place() {
+        let mut boss = Boss::new(1);
+        assert!(boss.shoot().is_empty());
+        let mut boss = hovering(1);
+        assert_eq!(boss.shoot().len(), 5);
+    }
+
+    #[test]
+    fn phases_move_on_as_it_loses_health() {
+        let mut boss = hovering(1);
+        assert_eq!(boss.phase(), Phase::Sweep);
+        boss.health = 26;
+        assert_eq!(boss.phase(), Phase::Spiral);
+        boss.health = 13;
+        assert_eq!(boss.phase(), Phase::Frenzy);
+        assert_eq!(boss.shoot().len(), 12);
+    }
+
+    #[test]
+    fn never_sways_off_screen() {
+        let mut boss = hovering(1);
+        for health in [40, 20, 5] {
+            boss.health = health;
+            for _ in 0..600 {
+                boss.update(0);
+                assert!(boss.left() >= 0 && boss.right() <= 160, "{:?}", boss.pos);
+            }
+        }
+    }
+
+    #[test]
+    fn blows_up_for_a_while_before_dying() {
+        let mut boss = hovering(1);
+        boss.kill();
+        assert!(boss.dying() && !boss.dead());
+        assert!(boss.shoot().is_empty());
+        for _ in 0..DEATH_FRAMES {
+            boss.update(0);
+        }
+        assert!(boss.dead());
+    }
+}
@@ -15,6 +15,8 @@ pub struct Bullet {
 
     pub dead: bool,
     pub damage: u32,
+    /// The player who fired it, for player bullets.
+    pub owner: usize,
 }
 
 impl Bullet {
@@ -32,8 +34,16 @@ impl Bullet {
             counter: 0,
             dead: false,
             damage: 1,
+            owner: 0,
         }
     }
+
+    /// Sends the bullet off at `speed`, `angle` radians from straight down,
+    /// with positive angles towards the right.
+    pub fn heading(mut self, angle: f32, speed: f32) -> Self {
+        self.vel = (speed * angle.sin(), speed * angle.cos());
+        self
+    }
 }
 
 impl Render for Bullet {
@@ -51,7 +61,7 @@ impl Movement for Bullet {
 
     fn update(&mut self, _frame: u32) { 
         self.counter += 1;
-        if self.counter % ANIMATION_SPEED == 0 {
+        if self.counter.is_multiple_of(ANIMATION_SPEED) {
             self.state += 1;
         }
         self.advance() 
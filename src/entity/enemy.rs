@@ -1,15 +1,103 @@
 use super::*;
-use crate::Random;
+use crate::sound;
+use crate::{Difficulty, Random};
 
+/// Frames between kite sprites.
+const FLUTTER_FRAMES: u32 = 15;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum State {
+    Moving,
+    Stationary,
+    Firing,
+    Leaving,
+    Dying,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ShootState {
+    Single,
+    First,
+    Second,
+}
+
+/// Which way an enemy's bullets go.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Aim {
+    /// Straight down.
+    Down,
+    /// At the player, wherever they are when the shot is fired.
+    Player,
+}
+
+/// Most an aimed shot turns away from straight down, so the player can't be
+/// shot at from the side.
+const MAX_AIM: f32 = 1.0;
+
+/// The kind of enemy, along with anything only that kind keeps track of.
+#[derive(Clone, PartialEq, Debug)]
 pub enum EnemyType {
+    /// Flies straight down, firing straight down.
     Blaster,
+    /// Moves into position and then continues fire.
     Turret {
         target_height: f32,
+        still_counter: u32,
+        shoot_state: ShootState,
+    },
+    /// Sways side to side while drifting down, dropping slow bullets.
+    Kite {
+        /// How far the kite sways either side of where it spawned.
+        sway_width: f32,
+        /// Radians of sway per frame.
+        sway_speed: f32,
+        /// Drop a pair of bullets instead of one.
+        double: bool,
     },
+}
+
+/// The kinds of enemy, without any per-enemy state.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum EnemyKind {
+    Blaster,
+    Turret,
     Kite,
 }
 
 impl EnemyType {
+    /// A freshly spawned enemy of `kind`.
+    pub fn new(kind: EnemyKind, random: &mut Random) -> Self {
+        match kind {
+            EnemyKind::Blaster => EnemyType::Blaster,
+            EnemyKind::Turret => EnemyType::turret(random),
+            EnemyKind::Kite => EnemyType::kite(random),
+        }
+    }
+
+    pub fn kind(&self) -> EnemyKind {
+        match self {
+            EnemyType::Blaster => EnemyKind::Blaster,
+            EnemyType::Turret{ .. } => EnemyKind::Turret,
+            EnemyType::Kite{ .. } => EnemyKind::Kite,
+        }
+    }
+
+    pub fn turret(random: &mut Random) -> Self {
+        EnemyType::Turret {
+            target_height: random.in_range(20, 60) as f32,
+            still_counter: 0,
+            shoot_state: ShootState::Single,
+        }
+    }
+
+    pub fn kite(random: &mut Random) -> Self {
+        EnemyType::Kite {
+            sway_width: random.in_range(15, 25) as f32,
+            sway_speed: 0.0,
+            double: false,
+        }
+    }
+
     fn sprite_list(&self) -> Vec<Sprite> {
         use EnemyType::*;
         match self {
@@ -30,7 +118,7 @@ impl EnemyType {
                 Sprite::turret8,
                 Sprite::turret9,
             ],
-            Kite => vec![
+            Kite{ .. } => vec![
                 Sprite::kite1,
                 Sprite::kite2,
             ],
@@ -40,27 +128,49 @@ impl EnemyType {
     fn starting_health(&self) -> u32 {
         use EnemyType::*;
         match &self {
-            Turret{ .. } => 3,
-            _ => 1,
+            Turret{ .. } => 4,
+            Kite{ .. } => 2,
+            Blaster => 1,
         }
     }
 
-    fn velocity(&self, cycle: Cycle) -> (f32, f32) {
+    fn spawn_x(&self, random: &mut Random, cycle: Cycle, player: &Player) -> f32 {
         use EnemyType::*;
         use Cycle::*;
         match (&self, cycle) {
-            (Blaster, Day) => (0.0, 0.5),
-            (Blaster, Night) => (0.0, 1.0),
-            _ => (0.0, 0.0),
+            (Blaster, Night) => random.in_range(
+                6.max(player.pos.0 as i32 - 40) as u32,
+                (160 - 6).min(player.pos.0 as i32 + 40) as u32,
+            ) as f32,
+            (Blaster, Day) => random.in_range(6, 160 - 6) as f32,
+            (Turret{ .. }, _) => random.in_range(20, 140) as f32,
+            (Kite{ .. }, _) => random.in_range(30, 130) as f32,
         }
     }
 
+    /// Velocity when spawned or when the cycle changes.  `None` leaves the
+    /// velocity alone when the cycle changes.
+    fn velocity(&self, cycle: Cycle) -> Option<(f32, f32)> {
+        use EnemyType::*;
+        use Cycle::*;
+        match (&self, cycle) {
+            (Blaster, Day) => Some((0.0, 0.5)),
+            (Blaster, Night) => Some((0.0, 1.0)),
+            (Turret{ .. }, _) => None,
+            (Kite{ .. }, Day) => Some((0.0, 0.3)),
+            (Kite{ .. }, Night) => Some((0.0, 0.45)),
+        }
+    }
+
+    /// Frames between shots.
     fn shoot_freq(&self, cycle: Cycle) -> u32 {
         use EnemyType::*;
         use Cycle::*;
         match (&self, cycle) {
             (Blaster, Day) => 120,
             (Blaster, Night) => 90,
+            (Kite{ .. }, Day) => 150,
+            (Kite{ .. }, Night) => 110,
             _ => 120,
         }
     }
@@ -71,43 +181,421 @@ impl EnemyType {
         match (&self, cycle) {
             (Blaster, Day) => 1.2,
             (Blaster, Night) => 2.0,
-            _ => 1.0,
+            (Turret{ .. }, _) => 1.5,
+            (Kite{ .. }, Day) => 0.7,
+            (Kite{ .. }, Night) => 0.9,
+        }
+    }
+
+    fn aim(&self, cycle: Cycle) -> Aim {
+        use EnemyType::*;
+        use Cycle::*;
+        match (&self, cycle) {
+            (Blaster, Night) => Aim::Player,
+            _ => Aim::Down,
+        }
+    }
+
+    /// Where the fire counter starts, a head start on the first shot.
+    fn starting_fire_counter(&self) -> i32 {
+        use EnemyType::*;
+        match self {
+            Blaster => 90,
+            Turret{ .. } => 0,
+            Kite{ .. } => 30,
+        }
+    }
+
+    /// Frames spent dying before being dead.
+    fn death_frames(&self) -> u32 {
+        use EnemyType::*;
+        match self {
+            Turret{ .. } => 39,
+            _ => 20,
+        }
+    }
+
+    /// Chance of dropping a powerup when killed during the day, before
+    /// accounting for the player's health.
+    pub fn drop_chance(&self) -> f32 {
+        use EnemyType::*;
+        match self {
+            Turret{ .. } => 0.18,
+            _ => 0.15,
+        }
+    }
+
+    /// Updates anything kept by the kind when the cycle changes.
+    fn mutate(&mut self, cycle: Cycle) {
+        use EnemyType::*;
+        match self {
+            Blaster => {}
+            Turret{ shoot_state, .. } => {
+                *shoot_state = match cycle {
+                    Cycle::Day => ShootState::Single,
+                    Cycle::Night => ShootState::First,
+                };
+            }
+            Kite{ sway_speed, double, .. } => {
+                *sway_speed = match cycle {
+                    Cycle::Day => 0.03,
+                    Cycle::Night => 0.05,
+                };
+                *double = cycle == Cycle::Night;
+            }
         }
     }
 }
 
+/// Any enemy.  What it looks like and how it moves and shoots is decided by
+/// its `EnemyType`.
+#[derive(Clone, Debug)]
 pub struct Enemy {
+    pub t: EnemyType,
     sprites: Vec<Sprite>,
-    t: EnemyType,
+    state: State,
     pos: (f32, f32),
     vel: (f32, f32),
     health: u32,
     shoot_freq: u32,
+    bullet_speed: f32,
+    aim: Aim,
+    /// Where the player is, for enemies that aim.
+    target: (f32, f32),
+    fire_counter: i32,
+    /// Frames a turret waits at its target height before leaving.
+    still_cap: u32,
 
     time_alive: u32,
     dying_counter: u32,
 }
 
 impl Enemy {
-    pub fn spawn(t: EnemyType, random: &mut Random, cycle: Cycle) -> Self {
-        let enemy = Self {
+    pub fn spawn(t: EnemyType, random: &mut Random, cycle: Cycle, player: &Player, difficulty: &Difficulty) -> Self {
+        let mut enemy = Self {
             sprites: t.sprite_list(),
-            pos: (0.0, 0.0),
-            vel: t.velocity(cycle),
+            state: State::Moving,
+            pos: (t.spawn_x(random, cycle, player), -5.0),
+            vel: t.velocity(cycle).unwrap_or((0.0, 0.4)),
             health: t.starting_health(),
-
-            shoot_freq: t.shoot_freq(cycle),
+            shoot_freq: 0,
+            bullet_speed: 0.0,
+            aim: Aim::Down,
+            target: (80.0, 160.0),
+            fire_counter: t.starting_fire_counter(),
+            still_cap: difficulty.still_cap,
 
             time_alive: 0,
             dying_counter: 0,
             t,
         };
+        enemy.mutate(cycle);
+        // turrets only take up double fire when the cycle turns, not when
+        // they spawn into a night
+        if let EnemyType::Turret{ shoot_state, .. } = &mut enemy.t {
+            *shoot_state = ShootState::Single;
+        }
         enemy
     }
 
-    /// Updates the enemy based on a change in cycle.
-    pub fn mutate(&mut self, cycle: Cycle) {
+    /// Tells the enemy where the player is, for aiming.
+    pub fn aim_at(&mut self, target: (f32, f32)) {
+        self.target = target;
+    }
+
+    /// Frames to wait between shots.
+    fn fire_cap(&self) -> i32 {
+        use ShootState::*;
+        match self.t {
+            EnemyType::Turret{ shoot_state, .. } => match (shoot_state, self.health) {
+                (Single, 1) => 60,
+                (First, 1) => 5,
+                (Second, 1) => 30,
+
+                (Single, _) => 120,
+                (First, _) => 5,
+                (Second, _) => 60,
+            },
+            _ => self.shoot_freq as i32,
+        }
+    }
+
+    /// The pattern and number of bullets for a single shot.
+    fn volley(&mut self) -> (Pattern, u32) {
+        match &mut self.t {
+            EnemyType::Blaster => (Pattern::Fan { spread: 0.0 }, 1),
+            EnemyType::Turret{ shoot_state, .. } => {
+                self.state = State::Firing;
+                self.fire_counter = -10;
+                *shoot_state = match shoot_state {
+                    ShootState::First => ShootState::Second,
+                    ShootState::Second => ShootState::First,
+                    ShootState::Single => ShootState::Single,
+                };
+                let count = if self.health > 2 { 2 } else { 1 };
+                (Pattern::Staggered { gap: 8.0, stagger: false }, count)
+            }
+            EnemyType::Kite{ double: true, .. } => (Pattern::Fan { spread: 0.7 }, 2),
+            EnemyType::Kite{ .. } => (Pattern::Fan { spread: 0.0 }, 1),
+        }
+    }
+
+    /// Movement for each kind, run every frame before advancing.
+    fn behave(&mut self) {
+        match &mut self.t {
+            EnemyType::Blaster => {}
+            EnemyType::Turret{ target_height, still_counter, .. } => {
+                if self.health <= 2 || (self.pos.1 > *target_height && *still_counter < self.still_cap) {
+                    self.vel.1 = 0.0;
+                    if self.fire_counter > 0 {
+                        self.state = State::Stationary;
+                    }
+                }
+
+                if self.pos.1 > *target_height {
+                    *still_counter += 1;
+                    if *still_counter >= self.still_cap {
+                        self.state = State::Leaving;
+                        if self.health > 2 {
+                            self.vel.1 = 2.0;
+                        } else {
+                            self.vel.1 = 1.5;
+                        }
+                    }
+                }
+            }
+            EnemyType::Kite{ sway_width, sway_speed, .. } => {
+                if self.dying_counter == 0 {
+                    // derivative of sway_width * sin(time_alive * sway_speed)
+                    let phase = self.time_alive as f32 * *sway_speed;
+                    self.vel.0 = *sway_width * *sway_speed * phase.cos();
+                } else {
+                    self.vel.0 = 0.0;
+                }
+            }
+        }
+    }
+}
+
+impl CycleDependent for Enemy {
+    fn mutate(&mut self, cycle: Cycle) {
+        self.t.mutate(cycle);
         self.shoot_freq = self.t.shoot_freq(cycle);
-        self.vel = self.t.velocity(cycle);
+        self.bullet_speed = self.t.bullet_speed(cycle);
+        self.aim = self.t.aim(cycle);
+        if let Some(vel) = self.t.velocity(cycle) {
+            self.vel = vel;
+        }
+    }
+}
+
+impl Alive for Enemy {
+    fn dead(&self) -> bool {
+        self.dying_counter > self.t.death_frames()
+    }
+
+    fn dying(&self) -> bool {
+        self.dying_counter > 0
+    }
+
+    fn health(&self) -> u32 {
+        self.health
+    }
+
+    fn health_mut(&mut self) -> &mut u32 {
+        &mut self.health
+    }
+
+    fn kill(&mut self) {
+        sound::enemy_death();
+        self.state = State::Dying;
+        self.dying_counter += 1;
+    }
+}
+
+impl Shoot for Enemy {
+    fn shoot(&mut self) -> Vec<Bullet> {
+        // blasters and turrets can still get a last shot off as they die
+        let ready = match self.t {
+            EnemyType::Turret{ .. } => {
+                self.state != State::Leaving && self.fire_counter > self.fire_cap()
+            }
+            EnemyType::Kite{ .. } => !self.dying() && self.fire_counter >= self.fire_cap(),
+            EnemyType::Blaster => self.fire_counter >= self.fire_cap(),
+        };
+        if !ready {
+            return vec![];
+        }
+
+        sound::enemy_fire();
+        self.fire_counter = 0;
+        let (pattern, count) = self.volley();
+        let mut origin = (self.pos.0, self.bottom() as f32);
+        if let EnemyType::Turret{ .. } = self.t {
+            // the guns sit just below the turret, and a damaged turret only
+            // has its left one
+            origin.1 += 1.0;
+            if count == 1 {
+                origin.0 -= 4.0;
+            }
+        }
+        let phase = match self.aim {
+            Aim::Down => 0.0,
+            Aim::Player => util::clamp(util::angle_to(origin, self.target), -MAX_AIM, MAX_AIM),
+        };
+        pattern.fire(origin, count, self.bullet_speed, phase)
+    }
+}
+
+impl Render for Enemy {
+    fn pos(&self) -> (f32, f32) { self.pos }
+
+    fn sprite(&self) -> Sprite {
+        use State::*;
+        let idx = match (&self.t, self.state) {
+            (EnemyType::Blaster, Dying) => match self.dying_counter {
+                x if x < 7 => 1,
+                x if x < 15 => 2,
+                _ => 3,
+            },
+            (EnemyType::Blaster, _) => 0,
+
+            (EnemyType::Turret{ .. }, Moving | Leaving) => if self.health > 2 {
+                (self.pos.1.abs() / 2.0) as usize % 3
+            } else {
+                4
+            }
+            (EnemyType::Turret{ .. }, Stationary) => if self.health <= 2 {
+                4
+            } else {
+                0
+            }
+            (EnemyType::Turret{ .. }, Firing) => if self.health <= 2 {
+                5
+            } else {
+                3
+            }
+            (EnemyType::Turret{ .. }, Dying) => if self.dying_counter > 30 {
+                8
+            } else {
+                self.dying_counter as usize / 10 + 5
+            }
+
+            (EnemyType::Kite{ .. }, _) => (self.time_alive / FLUTTER_FRAMES) as usize % 2,
+        };
+
+        self.sprites[idx]
+    }
+
+    fn draw(&self) {
+        // kites flicker while dying, since there are no death sprites
+        if let EnemyType::Kite{ .. } = self.t {
+            if self.dying() && !(self.dying_counter / 3).is_multiple_of(2) {
+                return;
+            }
+        }
+        color::set_draw(0x4320);
+        let (x, y) = self.screen_pos();
+        self.sprite().get().draw(x, y);
+    }
+}
+
+impl Movement for Enemy {
+    fn pos_mut(&mut self) -> &mut (f32, f32) { &mut self.pos }
+    fn vel(&self) -> (f32, f32) { self.vel }
+    fn vel_mut(&mut self) -> &mut (f32, f32) { &mut self.vel }
+
+    fn update(&mut self, _: u32) {
+        self.behave();
+        if self.dying() {
+            self.dying_counter += 1;
+            self.state = State::Dying;
+        } else {
+            self.time_alive += 1;
+            self.fire_counter += 1;
+        }
+        self.advance();
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+
+    fn spawn(t: fn(&mut Random) -> EnemyType, cycle: Cycle) -> Enemy {
+        let mut random = Random::seed(1);
+        Enemy::spawn(t(&mut random), &mut random, cycle, &Player::new(), &Difficulty::default())
+    }
+
+    fn blaster(_: &mut Random) -> EnemyType {
+        EnemyType::Blaster
+    }
+
+    /// Shoots as if the enemy had waited as long as it needs to.
+    fn fire(enemy: &mut Enemy) -> Vec<Bullet> {
+        enemy.fire_counter = enemy.fire_cap() + 1;
+        enemy.shoot()
+    }
+
+    #[test]
+    fn turrets_take_up_double_fire_when_night_falls() {
+        let mut turret = spawn(EnemyType::turret, Cycle::Night);
+        assert_eq!(turret.fire_cap(), 120);
+        turret.mutate(Cycle::Night);
+        assert_eq!(turret.fire_cap(), 5);
+    }
+
+    #[test]
+    fn damaged_turrets_fire_from_their_left_gun() {
+        let mut turret = spawn(EnemyType::turret, Cycle::Day);
+        let bottom = turret.bottom() as f32 + 1.0;
+        let xs: Vec<(f32, f32)> = fire(&mut turret).iter().map(|b| b.pos).collect();
+        assert_eq!(xs, [(turret.pos.0 - 4.0, bottom), (turret.pos.0 + 4.0, bottom)]);
+
+        turret.health = 2;
+        let xs: Vec<(f32, f32)> = fire(&mut turret).iter().map(|b| b.pos).collect();
+        assert_eq!(xs, [(turret.pos.0 - 4.0, bottom)]);
+    }
+
+    #[test]
+    fn kites_drop_a_pair_at_night() {
+        let mut kite = spawn(EnemyType::kite, Cycle::Day);
+        assert_eq!(fire(&mut kite).len(), 1);
+        kite.mutate(Cycle::Night);
+        assert_eq!(fire(&mut kite).len(), 2);
+    }
+
+    #[test]
+    fn only_kites_hold_fire_while_dying() {
+        for t in [blaster, EnemyType::turret, EnemyType::kite] {
+            let mut enemy = spawn(t, Cycle::Night);
+            enemy.kill();
+            let kite = matches!(enemy.t, EnemyType::Kite{ .. });
+            assert_eq!(fire(&mut enemy).is_empty(), kite);
+        }
+    }
+
+    #[test]
+    fn blasters_aim_at_the_player_at_night() {
+        let mut enemy = spawn(blaster, Cycle::Day);
+        enemy.aim_at((enemy.pos.0 + 50.0, 160.0));
+        assert_eq!(fire(&mut enemy)[0].vel.0, 0.0);
+
+        enemy.mutate(Cycle::Night);
+        let vel = fire(&mut enemy)[0].vel;
+        assert!(vel.0 > 0.0 && vel.1 > 0.0, "{vel:?}");
+
+        // but never from the side
+        enemy.aim_at((enemy.pos.0 + 100.0, enemy.pos.1));
+        let vel = fire(&mut enemy)[0].vel;
+        assert!((vel.0.atan2(vel.1) - MAX_AIM).abs() < 1e-6, "{vel:?}");
+    }
+
+    #[test]
+    fn kites_spread_their_pair_at_night() {
+        let mut kite = spawn(EnemyType::kite, Cycle::Night);
+        kite.mutate(Cycle::Night);
+        let vels: Vec<f32> = fire(&mut kite).iter().map(|b| b.vel.0).collect();
+        assert!(vels[0] < 0.0 && vels[1] > 0.0, "{vels:?}");
     }
 }
@@ -0,0 +1,130 @@
+use super::*;
+
+/// Pixels along each side of a cell.
+const CELL: i32 = 16;
+
+/// Cells along each side of the screen.
+const CELLS: i32 = 160 / CELL;
+
+/// A uniform grid over the screen, bucketing entities by the cells their
+/// bounds cover.  It is rebuilt every frame so that only entities near each
+/// other are given the pixel test in `collides_with`.  Anything partly or
+/// fully off screen goes in the nearest cells along the edge.
+#[derive(Clone, Debug)]
+pub struct Grid {
+    cells: Vec<Vec<usize>>,
+    /// A bit for each item, set by `near` for the items it finds.
+    marks: Vec<u64>,
+}
+
+impl Default for Grid {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Grid {
+    pub fn new() -> Self {
+        Self {
+            cells: vec![Vec::new(); (CELLS * CELLS) as usize],
+            marks: Vec::new(),
+        }
+    }
+
+    /// Empties the grid and fills it with the index of every item, keeping
+    /// the memory from last time.
+    pub fn rebuild<T: Render>(&mut self, items: &[T]) {
+        self.cells.iter_mut().for_each(|c| c.clear());
+        for (i, item) in items.iter().enumerate() {
+            for cell in Self::covered(item) {
+                self.cells[cell].push(i);
+            }
+        }
+        self.marks.clear();
+        self.marks.resize(items.len().div_ceil(64), 0);
+    }
+
+    /// Indices of everything that could be touching `item`, in order and
+    /// without repeats, so going through them gives the same result as going
+    /// through every item.
+    pub fn near<T: Render>(&mut self, item: &T) -> impl Iterator<Item = usize> + '_ {
+        self.marks.fill(0);
+        for cell in Self::covered(item) {
+            for &i in &self.cells[cell] {
+                self.marks[i / 64] |= 1 << (i % 64);
+            }
+        }
+        self.marks.iter().enumerate().flat_map(|(word, &bits)| {
+            let mut bits = bits;
+            core::iter::from_fn(move || {
+                let bit = bits.trailing_zeros() as usize;
+                bits &= bits.wrapping_sub(1);
+                (bit < 64).then_some(word * 64 + bit)
+            })
+        })
+    }
+
+    /// The cells covered by the bounds of `item`.
+    fn covered<T: Render>(item: &T) -> impl Iterator<Item = usize> {
+        let cell = |v: i32| v.div_euclid(CELL).clamp(0, CELLS - 1);
+        let (left, right) = (cell(item.left()), cell(item.right() - 1));
+        let (top, bottom) = (cell(item.top()), cell(item.bottom() - 1));
+        (top..=bottom).flat_map(move |y| (left..=right).map(move |x| (y * CELLS + x) as usize))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Random;
+
+    /// Somewhere on screen or a little way off it.
+    fn random_pos(random: &mut Random) -> (f32, f32) {
+        (random.in_range(0, 200) as f32 - 20.0, random.in_range(0, 200) as f32 - 20.0)
+    }
+
+    #[test]
+    fn same_pairs_as_every_pair() {
+        let mut random = Random::seed(1);
+        let mut grid = Grid::new();
+        for _ in 0..50 {
+            let bullets: Vec<Bullet> = (0..150).map(|_| Bullet::new(random_pos(&mut random))).collect();
+            let powerups: Vec<PowerUp> = (0..30)
+                .map(|_| PowerUp::spawn(PowerType::Health, random_pos(&mut random)))
+                .collect();
+
+            let mut every_pair = Vec::new();
+            for (p, powerup) in powerups.iter().enumerate() {
+                for (b, bullet) in bullets.iter().enumerate() {
+                    if powerup.collides_with(bullet) {
+                        every_pair.push((p, b));
+                    }
+                }
+            }
+
+            grid.rebuild(&bullets);
+            let mut grid_pairs = Vec::new();
+            for (p, powerup) in powerups.iter().enumerate() {
+                for b in grid.near(powerup) {
+                    if powerup.collides_with(&bullets[b]) {
+                        grid_pairs.push((p, b));
+                    }
+                }
+            }
+
+            assert_eq!(grid_pairs, every_pair);
+        }
+    }
+
+    #[test]
+    fn near_is_sorted_without_repeats() {
+        let mut random = Random::seed(2);
+        let bullets: Vec<Bullet> = (0..200).map(|_| Bullet::new(random_pos(&mut random))).collect();
+        let mut grid = Grid::new();
+        grid.rebuild(&bullets);
+        let wide = PowerUp::spawn(PowerType::Health, (80.0, 80.0));
+        let near: Vec<usize> = grid.near(&wide).collect();
+        assert!(near.windows(2).all(|w| w[0] < w[1]));
+        assert!(near.iter().all(|&i| i < bullets.len()));
+    }
+}
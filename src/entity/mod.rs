@@ -1,6 +1,7 @@
 use crate::Sprite;
 use crate::util;
 use crate::color;
+use crate::camera;
 use crate::Cycle;
 
 mod player;
@@ -12,14 +13,17 @@ pub use bullet::Bullet;
 mod powerup;
 pub use powerup::{PowerUp, PowerType};
 
-mod blaster;
-pub use blaster::Blaster;
+mod enemy;
+pub use enemy::{Enemy, EnemyKind, EnemyType};
 
-mod turret;
-pub use turret::Turret;
+mod boss;
+pub use boss::Boss;
 
-mod enemy;
-pub use enemy::*;
+mod pattern;
+pub use pattern::Pattern;
+
+mod grid;
+pub use grid::Grid;
 
 /// For entities that change during a cycle
 pub trait CycleDependent : Movement {
@@ -106,15 +110,23 @@ pub trait Render {
         self.bottom() < 0 || self.top() > 160 || self.left() > 160 || self.right() < 0
     }
 
+    /// Where the top left of the sprite is drawn, moved by any screen shake.
+    fn screen_pos(&self) -> (i32, i32) {
+        let (dx, dy) = camera::offset();
+        (self.left() + dx, self.top() + dy)
+    }
+
     /// Draws the sprite at the location.
     fn draw(&self) {
         color::set_draw(0x4320);
-        self.sprite().get().draw(self.left(), self.top());
+        let (x, y) = self.screen_pos();
+        self.sprite().get().draw(x, y);
     }
 
     /// Checks if the current entity collides with another entity.  This is
-    /// done by looping over the interesection in sprites and seeing if
-    /// there each sprite has a non-transparent pixel in the same location.
+    /// done by lining up the opacity masks of both sprites over their
+    /// intersection and seeing if any row has a non-transparent pixel in
+    /// the same location.
     fn collides_with<T: Render>(&self, other: &T) -> bool {
         let x_overlap = util::range_intersection(
             self.left(), self.right(),
@@ -127,21 +139,13 @@ pub trait Render {
 
         match (x_overlap, y_overlap) {
             (Some((left, right)), Some((top, bottom))) => {
-                let self_iter = self.sprite().get().range_iterator(
-                    left - self.left(),
-                    right - self.left(),
-                    top - self.top(),
-                    bottom - self.top(),
-                );
-                let other_iter = other.sprite().get().range_iterator(
-                    left - other.left(),
-                    right - other.left(),
-                    top - other.top(),
-                    bottom - other.top(),
-                );
-                self_iter.zip(other_iter).any(|(s, o)| {
-                    s != 0 && o != 0
-                })
+                self.sprite().get().overlaps(
+                    (left - self.left(), top - self.top()),
+                    other.sprite().get(),
+                    (left - other.left(), top - other.top()),
+                    (right - left) as u32,
+                    (bottom - top) as u32,
+                )
             }
             _ => false,
         }
@@ -179,3 +183,52 @@ pub trait Alive {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A sprite standing still somewhere.
+    struct Placed(Sprite, (f32, f32));
+
+    impl Render for Placed {
+        fn sprite(&self) -> Sprite {
+            self.0
+        }
+
+        fn pos(&self) -> (f32, f32) {
+            self.1
+        }
+    }
+
+    /// The pixel by pixel check collisions used before there were masks.
+    fn pixels_collide(a: &Placed, b: &Placed) -> bool {
+        let (a_data, b_data) = (a.sprite().get(), b.sprite().get());
+        (a.top().max(b.top())..a.bottom().min(b.bottom())).any(|y| {
+            (a.left().max(b.left())..a.right().min(b.right())).any(|x| {
+                let a_pixel = a_data.pixel((x - a.left()) as usize, (y - a.top()) as usize);
+                let b_pixel = b_data.pixel((x - b.left()) as usize, (y - b.top()) as usize);
+                a_pixel != 0 && b_pixel != 0
+            })
+        })
+    }
+
+    #[test]
+    fn masks_match_pixels() {
+        for &first in Sprite::ALL {
+            for &second in Sprite::ALL {
+                let a = Placed(first, (40.0, 40.0));
+                for dy in -34..=34 {
+                    for dx in -34..=34 {
+                        let b = Placed(second, (40.0 + dx as f32, 40.0 + dy as f32));
+                        assert_eq!(
+                            a.collides_with(&b),
+                            pixels_collide(&a, &b),
+                            "{first:?} and {second:?} at ({dx}, {dy})",
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use super::*;
+use core::f32::consts::TAU;
+
+/// Each bullet in a staggered volley is this much slower than the last, as a
+/// proportion of `speed`.
+const STAGGER: f32 = 0.15;
+
+/// A shape of enemy fire.  Every pattern is fired from an origin with a
+/// number of bullets, a speed, and a phase in radians from straight down that
+/// turns the whole pattern, with positive angles towards the right.  Turning
+/// the phase a little every shot makes spirals and rotating rings.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Pattern {
+    /// Spaced evenly all the way around.
+    Radial,
+    /// Spread evenly across `spread` radians, centered on the phase.
+    Fan { spread: f32 },
+    /// Along one curling arm, each bullet turned `twist` radians further and
+    /// slower than the last.
+    Spiral { twist: f32 },
+    /// In a line across, `gap` pixels apart, all heading the same way.  With
+    /// `stagger` each is a little slower than the last so they land one after
+    /// another.
+    Staggered { gap: f32, stagger: bool },
+    /// Spaced evenly on a circle `radius` pixels out, flying outwards.
+    Ring { radius: f32 },
+}
+
+impl Pattern {
+    /// The bullets for a single shot.
+    pub fn fire(self, origin: (f32, f32), count: u32, speed: f32, phase: f32) -> Vec<Bullet> {
+        let n = count as f32;
+        (0..count)
+            .map(|i| {
+                let i = i as f32;
+                match self {
+                    Pattern::Radial => Bullet::new(origin).heading(phase + i * TAU / n, speed),
+                    Pattern::Fan { spread } => {
+                        let angle = if count > 1 { i * spread / (n - 1.0) - spread / 2.0 } else { 0.0 };
+                        Bullet::new(origin).heading(phase + angle, speed)
+                    }
+                    Pattern::Spiral { twist } => {
+                        Bullet::new(origin).heading(phase + i * twist, speed * (1.0 - i / (2.0 * n)))
+                    }
+                    Pattern::Staggered { gap, stagger } => {
+                        let x = origin.0 + (i - (n - 1.0) / 2.0) * gap;
+                        let slow = if stagger { (1.0 - i * STAGGER).max(0.25) } else { 1.0 };
+                        Bullet::new((x, origin.1)).heading(phase, speed * slow)
+                    }
+                    Pattern::Ring { radius } => {
+                        let angle = phase + i * TAU / n;
+                        let pos = (origin.0 + radius * angle.sin(), origin.1 + radius * angle.cos());
+                        Bullet::new(pos).heading(angle, speed)
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Radians from straight down each bullet is heading.
+    fn angles(bullets: &[Bullet]) -> Vec<f32> {
+        bullets.iter().map(|b| b.vel.0.atan2(b.vel.1)).collect()
+    }
+
+    fn assert_close(a: &[f32], b: &[f32]) {
+        assert_eq!(a.len(), b.len());
+        assert!(a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-5), "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn radial_spaces_bullets_all_the_way_around() {
+        let bullets = Pattern::Radial.fire((80.0, 40.0), 4, 1.0, 0.1);
+        assert!(bullets.iter().all(|b| b.pos == (80.0, 40.0)));
+        let turns: Vec<f32> = bullets.iter().map(|b| (b.vel.0.atan2(b.vel.1) - 0.1).rem_euclid(TAU)).collect();
+        assert_close(&turns, &[0.0, TAU / 4.0, TAU / 2.0, 3.0 * TAU / 4.0]);
+    }
+
+    #[test]
+    fn fans_center_on_the_phase() {
+        assert_close(&angles(&Pattern::Fan { spread: 1.0 }.fire((0.0, 0.0), 3, 1.0, 0.2)), &[-0.3, 0.2, 0.7]);
+        assert_close(&angles(&Pattern::Fan { spread: 1.0 }.fire((0.0, 0.0), 1, 1.0, 0.2)), &[0.2]);
+    }
+
+    #[test]
+    fn staggered_bullets_line_up_across_and_slow_down() {
+        let bullets = Pattern::Staggered { gap: 10.0, stagger: true }.fire((80.0, 40.0), 3, 2.0, 0.0);
+        let xs: Vec<f32> = bullets.iter().map(|b| b.pos.0).collect();
+        let speeds: Vec<f32> = bullets.iter().map(|b| b.vel.1).collect();
+        assert_close(&xs, &[70.0, 80.0, 90.0]);
+        assert_close(&speeds, &[2.0, 1.7, 1.4]);
+    }
+
+    #[test]
+    fn rings_start_out_from_the_origin() {
+        for bullet in (Pattern::Ring { radius: 8.0 }).fire((80.0, 40.0), 6, 1.0, 0.0) {
+            let (dx, dy) = (bullet.pos.0 - 80.0, bullet.pos.1 - 40.0);
+            assert!((dx.hypot(dy) - 8.0).abs() < 1e-4);
+            // flying straight outwards
+            assert!((dx * bullet.vel.1 - dy * bullet.vel.0).abs() < 1e-4);
+        }
+    }
+}
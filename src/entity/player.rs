@@ -6,6 +6,9 @@ use crate::color;
 
 const TURN_FRAMES: i32 = 15;
 
+/// How long a spreader powerup lasts.
+const SPREADER_FRAMES: u32 = 600;
+
 #[derive(Clone, Debug)]
 pub enum PlayerState {
     Stationary,
@@ -27,6 +30,15 @@ pub struct Player {
     invincible_counter: u32,
     pub health: u32,
     pub speed: f32,
+    pub spreader_counter: u32,
+    /// Draw colors for the ship, so two players can tell theirs apart.
+    pub colors: u16,
+}
+
+impl Default for Player {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Player {
@@ -51,19 +63,33 @@ impl Player {
             invincible_counter: 0,
             health: 3,
             speed: 1.0,
+            spreader_counter: 0,
+            colors: 0x4320,
         }
     }
+
+    /// Brings a downed ship back with a single heart and a moment of
+    /// invincibility.  Powerups are lost.
+    pub fn revive(&mut self) {
+        *self = Self {
+            pos: (self.pos.0, 120.0),
+            health: 1,
+            invincible_counter: 120,
+            colors: self.colors,
+            ..Self::new()
+        };
+    }
     
     pub fn power_up(&mut self, powerup: PowerType) {
         match powerup {
             PowerType::Speed => { self.speed += 0.5 }
             PowerType::Health => { self.health += 1 }
-            _ => {}
+            PowerType::Spreader => { self.spreader_counter = SPREADER_FRAMES }
         }
     }
 
     pub fn move_left(&mut self) {
-        self.vel.0 = -1.0 * self.speed;
+        self.vel.0 = -self.speed;
     }
 
     pub fn move_right(&mut self) {
@@ -78,6 +104,16 @@ impl Player {
         self.vel.1 = 0.5 * self.speed;
     }
 
+    /// Is the spreader powerup active?
+    pub fn spreading(&self) -> bool {
+        self.spreader_counter > 0
+    }
+
+    /// Proportion of the spreader powerup left, from 0 to 1.
+    pub fn spreader_left(&self) -> f32 {
+        self.spreader_counter as f32 / SPREADER_FRAMES as f32
+    }
+
     pub fn total_speed_powerups(&self) -> u32 {
         match self.speed {
             1.5 => 1,
@@ -131,8 +167,7 @@ impl Shoot for Player {
             self.top() as f32,
         ));
         bullet.vel.1 = -2.0;
-        if false {
-            sound::player_fire();
+        if self.spreading() {
             let mut bullet2 = Bullet::new((
                 self.pos.0 - 4.0,
                 self.top() as f32,
@@ -175,9 +210,10 @@ impl Render for Player {
     }
 
     fn draw(&self) {
-        if (self.invincible_counter / 5) % 2 == 0 {
-            color::set_draw(0x4320);
-            self.sprite().get().draw(self.left(), self.top());
+        if (self.invincible_counter / 5).is_multiple_of(2) {
+            color::set_draw(self.colors);
+            let (x, y) = self.screen_pos();
+            self.sprite().get().draw(x, y);
         }
     }
 }
@@ -198,6 +234,10 @@ impl Movement for Player {
             self.invincible_counter -= 1;
         }
 
+        if self.spreader_counter > 0 {
+            self.spreader_counter -= 1;
+        }
+
         // update movement counter based on speed
         if self.vel.0 < 0.0 {
             if self.movement_counter > -TURN_FRAMES {
@@ -231,3 +271,23 @@ impl Movement for Player {
     }
 
 }
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spreader_fires_three_until_it_runs_out() {
+        let mut player = Player::new();
+        assert_eq!(player.shoot().len(), 1);
+
+        player.power_up(PowerType::Spreader);
+        assert_eq!(player.shoot().len(), 3);
+        for _ in 0..SPREADER_FRAMES {
+            assert!(player.spreading());
+            player.update(0);
+        }
+        assert_eq!(player.spreader_left(), 0.0);
+        assert_eq!(player.shoot().len(), 1);
+    }
+}
@@ -1,5 +1,4 @@
 use super::*;
-use crate::Random;
 
 #[derive(Clone, Copy, PartialEq, Debug)]
 pub enum PowerType {
@@ -0,0 +1,148 @@
+//! Turning numbers into text without `core::fmt`, which costs a few
+//! kilobytes of cart size.  Lines are built on the stack and handed straight
+//! to `text`.
+
+/// The most characters that fit across the screen.
+const LEN: usize = 160 / 8;
+
+/// A line of text built up on the stack.  Anything past the edge of the
+/// screen is dropped.
+#[derive(Clone, Copy, Debug)]
+pub struct Line {
+    bytes: [u8; LEN],
+    len: usize,
+}
+
+impl Default for Line {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Line {
+    pub fn new() -> Self {
+        Self {
+            bytes: [0; LEN],
+            len: 0,
+        }
+    }
+
+    /// Adds `s` to the end of the line.
+    pub fn str(mut self, s: &str) -> Self {
+        for &b in s.as_bytes() {
+            self = self.byte(b);
+        }
+        self
+    }
+
+    /// Adds `n` to the end of the line.
+    pub fn num(self, n: u32) -> Self {
+        self.padded(n, 0)
+    }
+
+    /// Adds `n` to the end of the line, with spaces in front to take up at
+    /// least `width` characters.
+    pub fn padded(self, n: u32, width: usize) -> Self {
+        self.filled(n, width, b' ')
+    }
+
+    /// Adds `n` to the end of the line, with zeros in front to take up at
+    /// least `width` digits.
+    pub fn zeroed(self, n: u32, width: usize) -> Self {
+        self.filled(n, width, b'0')
+    }
+
+    fn filled(mut self, n: u32, width: usize, fill: u8) -> Self {
+        let mut digits = [0; 10];
+        let mut count = 0;
+        let mut n = n;
+        loop {
+            digits[count] = b'0' + (n % 10) as u8;
+            count += 1;
+            n /= 10;
+            if n == 0 {
+                break;
+            }
+        }
+
+        for _ in count..width {
+            self = self.byte(fill);
+        }
+        for &d in digits[..count].iter().rev() {
+            self = self.byte(d);
+        }
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.bytes[..self.len]).unwrap_or_default()
+    }
+
+    fn byte(mut self, b: u8) -> Self {
+        if self.len < LEN {
+            self.bytes[self.len] = b;
+            self.len += 1;
+        }
+        self
+    }
+}
+
+impl AsRef<str> for Line {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+/// `n` on its own.
+pub fn num(n: u32) -> Line {
+    Line::new().num(n)
+}
+
+/// `n` right aligned in `width` characters.
+pub fn padded(n: u32, width: usize) -> Line {
+    Line::new().padded(n, width)
+}
+
+/// `frames` as minutes and seconds, like "2:05".
+pub fn time(frames: u32) -> Line {
+    let seconds = frames / 60;
+    Line::new().num(seconds / 60).str(":").zeroed(seconds % 60, 2)
+}
+
+/// `label` followed by a space and `n`, like "Day 3".
+pub fn labelled(label: &str, n: u32) -> Line {
+    Line::new().str(label).str(" ").num(n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers() {
+        assert_eq!(num(0).as_str(), "0");
+        assert_eq!(num(u32::MAX).as_str(), "4294967295");
+        assert_eq!(padded(42, 5).as_str(), "   42");
+        assert_eq!(padded(123456, 3).as_str(), "123456");
+        assert_eq!(Line::new().zeroed(7, 3).as_str(), "007");
+        assert_eq!(labelled("Day", 12).as_str(), "Day 12");
+    }
+
+    #[test]
+    fn time_in_minutes_and_seconds() {
+        assert_eq!(time(0).as_str(), "0:00");
+        assert_eq!(time(125 * 60 + 59).as_str(), "2:05");
+        assert_eq!(time(61 * 60 * 60).as_str(), "61:00");
+    }
+
+    #[test]
+    fn drops_what_goes_off_screen() {
+        let line = Line::new().str("a score of").str(" ").num(1234567890).str("!");
+        assert_eq!(line.as_str(), "a score of 123456789");
+        assert_eq!(line.len(), LEN);
+    }
+}
@@ -1,6 +1,12 @@
 use crate::*;
+use crate::replay::Recording;
+use crate::save::Save;
+use crate::combo::Combo;
+use crate::stats::Stats;
+use crate::achievement::Tracker;
+use crate::particle::{Burst, Particles};
 
-#[derive(Clone, PartialEq, Copy)]
+#[derive(Clone, PartialEq, Copy, Debug)]
 pub enum Cycle {
     Day,
     Night,
@@ -13,111 +19,276 @@ pub enum State {
     DayTransition,
 }
 
-const CYCLE_LENGTH: u32 = 3600;
+/// What the game wants the app to do after a tick.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum GameAction {
+    Quit,
+}
+
+/// Something on the pause menu.
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum PauseItem {
+    Resume,
+    Restart,
+    Quit,
+}
+
+impl PauseItem {
+    const ALL: [PauseItem; 3] = [PauseItem::Resume, PauseItem::Restart, PauseItem::Quit];
+
+    fn label(self) -> &'static str {
+        match self {
+            PauseItem::Resume => "Resume",
+            PauseItem::Restart => "Restart",
+            PauseItem::Quit => "Quit to menu",
+        }
+    }
+}
+
+/// Who is playing, and how the score is kept with two players.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Mode {
+    Solo,
+    /// Two players adding to one score.
+    Shared,
+    /// Two players, each scoring their own kills.
+    Separate,
+}
+
+impl Mode {
+    pub fn players(self) -> usize {
+        match self {
+            Mode::Solo => 1,
+            Mode::Shared | Mode::Separate => 2,
+        }
+    }
+}
+
+/// Points for shooting something down, before the combo multiplier.
+const KILL_POINTS: u32 = 10;
+
+/// A ship, the gamepad flying it and what it has shot down.
+struct Pilot {
+    player: Player,
+    controls: Controls,
+    kills: u32,
+    /// Points from kills, with combos counted.
+    points: u32,
+    combo: Combo,
+    stats: Stats,
+}
+
+impl Pilot {
+    /// Player `idx` of `players`, flying with gamepad `idx`.
+    fn new(idx: usize, players: usize, seed: u32, difficulty: &Difficulty) -> Self {
+        let mut player = Player::new();
+        player.health = difficulty.starting_health;
+        if players > 1 {
+            player.pos.0 = [60.0, 100.0][idx];
+        }
+        if idx == 1 {
+            player.colors = 0x4230;
+        }
+        Self {
+            player,
+            controls: Controls::record(seed).on_gamepad(idx),
+            kills: 0,
+            points: 0,
+            combo: Combo::default(),
+            stats: Stats::default(),
+        }
+    }
+
+    /// Counts something this pilot shot down.
+    fn kill(&mut self) {
+        self.kills += 1;
+        self.points += self.combo.kill(KILL_POINTS);
+    }
+
+    /// Damages the ship, losing the combo if the hit lands.
+    fn hit(&mut self, amount: u32, particles: &mut Particles) {
+        let health = self.player.health();
+        self.player.damage(amount);
+        if self.player.health() < health {
+            self.combo.reset();
+            self.stats.damage_taken += health - self.player.health();
+            camera::shake(if self.player.dying() { 24 } else { 12 });
+            particles.burst(Burst::Hurt, self.player.pos);
+            if self.player.dying() {
+                particles.burst(Burst::Explosion, self.player.pos);
+            }
+        }
+    }
+}
+
+/// Pages on the end screen.
+const STATS_PAGES: usize = 4;
+
+/// Frames spent going from day to night or back.
+const TRANSITION_LENGTH: i32 = 119;
 
 pub struct Game {
     cycle: Cycle,
     state: State,
-    controls: Controls,
+    mode: Mode,
+    difficulty: Difficulty,
     frame: u32,
-    kills: u32,
     random: Random,
 
-    player: Player,
+    pilots: Vec<Pilot>,
     bullets: Vec<Bullet>,
     powerups: Vec<PowerUp>,
 
-    blasters: Vec<Blaster>,
-    turrets: Vec<Turret>,
+    enemies: Vec<Enemy>,
     enemy_bullets: Vec<Bullet>,
+    /// The end of night boss, while it is around.
+    boss: Option<Boss>,
+    /// Broad phase for collisions, refilled for each kind of entity.
+    grid: Grid,
+    particles: Particles,
 
     day: u32,
     spawn_cooldown: i32,
     time_alive: u32,
     cycle_counter: u32,
     transition_counter: i32,
+
+    /// The finished recordings of this run, one per player, once it is over.
+    recordings: Vec<Recording>,
+    /// Rank in the high score table, if this run made it in.
+    high_score: Option<usize>,
+    /// Cursor on the pause menu, while paused.
+    paused: Option<usize>,
+    /// Page of the end screen being shown.
+    stats_page: usize,
+    achievements: Tracker,
 }
 
 impl Game {
-    pub fn new(random: Random) -> Self {
-        Palette::Grey.set();
-        Self {
+    pub fn new(random: Random, mode: Mode, difficulty: Difficulty) -> Self {
+        Palette::Transition.set();
+        camera::reset();
+        let players = mode.players();
+        let mut game = Self {
             cycle: Cycle::Day,
             state: State::DayTransition,
-            controls: Controls::new(),
+            mode,
+            difficulty,
             frame: 0,
-            kills: 0,
+            pilots: (0..players)
+                .map(|i| Pilot::new(i, players, random.state(), &difficulty))
+                .collect(),
+            // a stream of its own, so effects don't change how the run goes
+            particles: Particles::new(random.state().rotate_left(16)),
             random,
 
-            player: Player::new(),
             bullets: Vec::new(),
             powerups: Vec::new(),
 
-            blasters: Vec::new(),
-            turrets: Vec::new(),
+            enemies: Vec::new(),
             enemy_bullets: Vec::new(),
+            boss: None,
+            grid: Grid::new(),
 
             day: 1,
             spawn_cooldown: 1,
             time_alive: 0,
             cycle_counter: 0,
             transition_counter: 60,
+
+            recordings: Vec::new(),
+            high_score: None,
+            paused: None,
+            stats_page: 0,
+            achievements: Tracker::new(Save::load().achievements),
+        };
+        // whatever started the run shouldn't also fire the first shot
+        for pilot in &mut game.pilots {
+            pilot.controls.ignore_held();
         }
+        game
+    }
+
+    /// Plays back a recorded run frame for frame, with a recording for each
+    /// player.  It has to be played at the difficulty it was recorded at.
+    pub fn replay(mode: Mode, difficulty: Difficulty, recordings: Vec<Recording>) -> Self {
+        let seed = recordings.first().map_or(0, |r| r.seed);
+        let mut game = Self::new(Random::from_state(seed), mode, difficulty);
+        for (pilot, recording) in game.pilots.iter_mut().zip(recordings) {
+            pilot.controls = Controls::replay(recording);
+        }
+        game
+    }
+
+    /// If the run just finished can be watched back, which needs every
+    /// player's input in full.
+    fn replayable(&self) -> bool {
+        !self.recordings.is_empty() && self.recordings.iter().all(|r| !r.truncated)
     }
 
-    /// Runs every frame, calls other functions to make the game function
-    pub fn tick(&mut self) {
+    /// The recordings of the run, one per player, available once the run is
+    /// over.
+    pub fn recordings(&self) -> &[Recording] {
+        &self.recordings
+    }
+
+    /// Runs every frame, calls other functions to make the game function.
+    /// `input` is the live gamepad, used for the pause menu so pausing never
+    /// ends up in a recording.
+    pub fn tick(&mut self, input: &Controls) -> Option<GameAction> {
+        if let Some(cursor) = self.paused {
+            return self.pause(input, cursor);
+        }
+        if !matches!(self.state, State::EndScreen) && input.pressed(Button::Secondary) {
+            self.paused = Some(0);
+            self.draw_paused(0);
+            return None;
+        }
+        // sit out the frame entirely, so the controls don't move on either
+        if !matches!(self.state, State::EndScreen) && camera::frozen() {
+            self.draw_hud();
+            self.draw();
+            self.achievements.draw_toast();
+            return None;
+        }
+        camera::update();
+        self.particles.update();
+
         self.spawn_cooldown -= 1;
         self.transition_counter -= 1;
 
         if let State::Play | State::EndScreen = self.state {
-            self.controls.next();
+            self.pilots.iter_mut().for_each(|p| p.controls.next());
         }
 
         // Print UI elements
         match self.state {
             State::EndScreen => {
-                color::set_draw(0x03);
-
-                match self.cycle {
-                    Cycle::Day => {
-                        text(format!("Day {}", self.day), 30, 30);
-                    }
-                    Cycle::Night => {
-                        text(format!("Night {}", self.day), 30, 30);
-                    }
+                self.draw_end_screen();
+                let controls = &self.pilots[0].controls;
+                if controls.pressed(Button::Left) {
+                    self.stats_page = (self.stats_page + STATS_PAGES - 1) % STATS_PAGES;
+                } else if controls.pressed(Button::Right) {
+                    self.stats_page = (self.stats_page + 1) % STATS_PAGES;
                 }
-
-                text("Final score:", 20, 50);
-                text(self.score().to_string(), 120, 50);
-                text("Total kills:", 20, 60);
-                text(self.kills.to_string(), 120, 60);
-                text("Press action to", 20, 100);
-                text("play again.", 20, 110);
-                if self.controls.pressed_or_held(Button::Primary) {
-                    *self = Self::new(self.random.clone());
+                if controls.pressed_or_held(Button::Primary) {
+                    *self = Self::new(self.random.clone(), self.mode, self.difficulty);
+                } else if controls.pressed(Button::Secondary) && self.replayable() {
+                    *self = Self::replay(self.mode, self.difficulty, core::mem::take(&mut self.recordings));
                 }
             }
             _ => {
-                self.cycle_counter += 1;
+                self.advance_cycle_counter();
                 self.resolve_controls();
-                color::set_draw(0x02);
-                if self.time_alive > 0 {
-                    text(self.score().to_string(), 1, 1);
-                    match self.cycle {
-                        Cycle::Day => {
-                            text(format!("Day {}", self.day), 1, 11);
-                        }
-                        Cycle::Night => {
-                            text(format!("Night {}", self.day), 1, 11);
-                        }
-                    }
-                }
+                self.draw_hud();
             }
         }
 
         if let State::Play = self.state {
             self.time_alive += 1;
+            for pilot in self.pilots.iter_mut().filter(|p| !p.player.dying()) {
+                pilot.stats.add_frame(self.cycle);
+            }
         }
 
         // Advance game
@@ -132,11 +303,7 @@ impl Game {
                 self.draw();
             }
             State::NightTransition => {
-                if self.transition_counter >= 60 {
-                    Palette::Grey.transition_to(((self.transition_counter - 60) / 15) as usize);
-                } else if self.transition_counter > 0 {
-                    Palette::Night.transition_to((self.transition_counter / 15) as usize);
-                }
+                color::blend(color::DUSK, self.transition_progress());
                 if self.transition_counter == 0 {
                     self.state = State::Play;
                     self.cycle = Cycle::Night;
@@ -145,11 +312,7 @@ impl Game {
                 self.draw_sun_moon();
             }
             State::DayTransition => {
-                if self.transition_counter >= 60 {
-                    Palette::Grey.transition_to(((self.transition_counter - 60) / 15) as usize);
-                } else if self.transition_counter > 0 {
-                    Palette::Day.transition_to((self.transition_counter / 15) as usize);
-                }
+                color::blend(color::DAWN, self.transition_progress());
                 if self.transition_counter == 0 {
                     self.state = State::Play;
                     self.cycle = Cycle::Day;
@@ -158,6 +321,192 @@ impl Game {
                 self.draw_sun_moon();
             }
         }
+
+        self.watch_achievements();
+        None
+    }
+
+    /// Unlocks any achievements earned so far and saves them, unless this is
+    /// a replay.
+    fn watch_achievements(&mut self) {
+        if !self.replaying() {
+            let unlocked = self.achievements.watch(self.cycle, self.day, &self.stats(), self.best_combo());
+            if unlocked != 0 {
+                let mut save = Save::load();
+                save.achievements |= unlocked;
+                save.store();
+            }
+        }
+        self.achievements.draw_toast();
+    }
+
+    /// Handles the pause menu, with the scene frozen underneath.
+    fn pause(&mut self, input: &Controls, mut cursor: usize) -> Option<GameAction> {
+        let items = PauseItem::ALL;
+        if input.pressed(Button::Up) {
+            cursor = (cursor + items.len() - 1) % items.len();
+        } else if input.pressed(Button::Down) {
+            cursor = (cursor + 1) % items.len();
+        }
+        self.paused = Some(cursor);
+
+        if input.pressed(Button::Primary) {
+            match items[cursor] {
+                PauseItem::Resume => self.resume(),
+                PauseItem::Restart => {
+                    *self = Self::new(self.random.clone(), self.mode, self.difficulty);
+                    return None;
+                }
+                PauseItem::Quit => return Some(GameAction::Quit),
+            }
+        } else if input.pressed(Button::Secondary) {
+            self.resume();
+        }
+
+        self.draw_paused(cursor);
+        None
+    }
+
+    /// Leaves the pause menu.  The button that closed it is probably still
+    /// held, and shouldn't fire a shot.
+    fn resume(&mut self) {
+        self.paused = None;
+        for pilot in &mut self.pilots {
+            pilot.controls.ignore_held();
+        }
+    }
+
+    /// Score, day and combo along the top of the screen.
+    fn draw_hud(&self) {
+        color::set_draw(0x02);
+        if self.time_alive > 0 {
+            match self.mode {
+                Mode::Separate => {
+                    text(format::num(self.pilot_score(0)), 1, 1);
+                    let p2 = format::num(self.pilot_score(1));
+                    text(p2, 160 - 8 * p2.len() as i32, 1);
+                }
+                Mode::Solo | Mode::Shared => text(format::num(self.score()), 1, 1),
+            }
+            match self.cycle {
+                Cycle::Day => {
+                    text(format::labelled("Day", self.day), 1, 11);
+                }
+                Cycle::Night => {
+                    text(format::labelled("Night", self.day), 1, 11);
+                }
+            }
+            for (i, pilot) in self.pilots.iter().enumerate() {
+                let multiplier = pilot.combo.multiplier();
+                if multiplier > 1 {
+                    let combo = format::Line::new().str("x").num(multiplier);
+                    let x = if i == 0 { 1 } else { 160 - 8 * combo.len() as i32 };
+                    text(combo, x, 21);
+                }
+            }
+            if self.replaying() {
+                let y = if self.mode == Mode::Separate { 11 } else { 1 };
+                text("Replay", 160 - 6 * 8, y);
+            }
+        }
+    }
+
+    /// The results of the run, a page at a time.
+    fn draw_end_screen(&self) {
+        color::set_draw(0x03);
+        let row = |label: &str, value: format::Line, y: i32| {
+            text(label, 20, y);
+            text(value, 120, y);
+        };
+        let stats = self.stats();
+
+        match self.stats_page {
+            0 => {
+                match self.cycle {
+                    Cycle::Day => text(format::labelled("Day", self.day), 30, 30),
+                    Cycle::Night => text(format::labelled("Night", self.day), 30, 30),
+                }
+                match self.mode {
+                    Mode::Separate => {
+                        row("P1 score:", format::num(self.pilot_score(0)), 50);
+                        row("P2 score:", format::num(self.pilot_score(1)), 60);
+                        row("Total kills:", format::num(self.kills()), 70);
+                        row("Best combo:", format::num(self.best_combo()), 80);
+                    }
+                    Mode::Solo | Mode::Shared => {
+                        row("Final score:", format::num(self.score()), 50);
+                        row("Total kills:", format::num(self.kills()), 60);
+                        row("Best combo:", format::num(self.best_combo()), 70);
+                    }
+                }
+                if let Some(rank) = self.high_score {
+                    color::set_draw(0x02);
+                    text(format::Line::new().str("New high score #").num(rank as u32 + 1), 12, 80);
+                    color::set_draw(0x03);
+                }
+            }
+            1 => {
+                text("Shooting", 30, 30);
+                row("Shots fired:", format::num(stats.shots), 50);
+                row("Hits:", format::num(stats.hits), 60);
+                row("Accuracy:", format::num(stats.accuracy()).str("%"), 70);
+                row("Health lost:", format::num(stats.damage_taken), 80);
+                row("Powerups:", format::num(stats.powerups), 90);
+            }
+            2 => {
+                text("Kills", 30, 30);
+                row("Blasters:", format::num(stats.kills(EnemyKind::Blaster)), 50);
+                row("Turrets:", format::num(stats.kills(EnemyKind::Turret)), 60);
+                row("Kites:", format::num(stats.kills(EnemyKind::Kite)), 70);
+                row("Bosses:", format::num(stats.bosses), 80);
+            }
+            _ => {
+                text("Time survived", 30, 30);
+                row("Days:", format::time(stats.frames(Cycle::Day)), 50);
+                row("Nights:", format::time(stats.frames(Cycle::Night)), 60);
+                let total = stats.frames(Cycle::Day) + stats.frames(Cycle::Night);
+                row("Total:", format::time(total), 70);
+            }
+        }
+
+        text("Press action to", 20, 100);
+        text("play again.", 20, 110);
+        if self.replayable() {
+            text("Press back to", 20, 125);
+            text("watch replay.", 20, 135);
+        } else if self.recordings.iter().any(|r| r.truncated) {
+            text("Run too long", 20, 125);
+            text("to replay.", 20, 135);
+        }
+        let page = format::Line::new()
+            .str("< ")
+            .num(self.stats_page as u32 + 1)
+            .str("/")
+            .num(STATS_PAGES as u32)
+            .str(" >");
+        text(page, 80 - 4 * page.len() as i32, 150);
+    }
+
+    fn draw_paused(&mut self, cursor: usize) {
+        self.draw_hud();
+        self.draw();
+        if let State::NightTransition | State::DayTransition = self.state {
+            self.draw_sun_moon();
+        }
+        color::dim();
+
+        color::set_draw(0x41);
+        rect(20, 50, 120, 54);
+        color::set_draw(0x03);
+        text("Paused", 56, 58);
+        let labels = PauseItem::ALL.map(PauseItem::label);
+        crate::menu::draw_items(&labels, cursor, 26, 74);
+        self.achievements.draw_toast();
+    }
+
+    /// How far through the current transition, from 0 to 1.
+    fn transition_progress(&self) -> f32 {
+        1.0 - self.transition_counter as f32 / TRANSITION_LENGTH as f32
     }
 
     fn draw_sun_moon(&mut self) {
@@ -167,11 +516,11 @@ impl Game {
         match self.state {
             State::DayTransition => {
                 color::set_draw(0x20);
-                Sprite::sun.get().draw(center.0 - 4, (center.1 - 10 + f / 10) as i32);
+                Sprite::sun.get().draw(center.0 - 4, center.1 - 10 + f / 10);
             }
             State::NightTransition => {
                 color::set_draw(0x20);
-                Sprite::moon.get().draw(center.0 - 4, (center.1 - 10 + f / 10) as i32);
+                Sprite::moon.get().draw(center.0 - 4, center.1 - 10 + f / 10);
             }
             _ => {}
         }
@@ -182,92 +531,166 @@ impl Game {
         match self.cycle {
             Cycle::Day => {
                 color::set_draw(0x01);
-                text(format!("Day {}", self.day), 58, 95);
+                text(format::labelled("Day", self.day), 58, 95);
                 color::set_draw(0x12);
-                text(format!("Day {}", self.day), 59, 96);
+                text(format::labelled("Day", self.day), 59, 96);
             }
             Cycle::Night => {
                 color::set_draw(0x01);
-                text(format!("Night {}", self.day), 52, 95);
+                text(format::labelled("Night", self.day), 52, 95);
                 color::set_draw(0x12);
-                text(format!("Night {}", self.day), 53, 96);
+                text(format::labelled("Night", self.day), 53, 96);
             }
         }
     }
 
+    /// Moves through the cycle, except the night doesn't end while the boss is
+    /// still around.
+    fn advance_cycle_counter(&mut self) {
+        let night_over = (self.cycle_counter + 1).is_multiple_of(self.difficulty.cycle_length);
+        if !night_over || self.boss.is_none() {
+            self.cycle_counter += 1;
+        }
+    }
+
     fn resolve_cycle(&mut self) {
-        if self.cycle_counter % CYCLE_LENGTH == CYCLE_LENGTH / 4 * 3 {
+        let cycle_length = self.difficulty.cycle_length;
+        if self.cycle_counter % cycle_length == self.difficulty.night_start() {
             // check if 45 passed seconds
             self.cycle = Cycle::Night;
             self.state = State::NightTransition;
-            self.blasters.iter_mut().for_each(|b| b.mutate(self.cycle));
-            self.turrets.iter_mut().for_each(|b| b.mutate(self.cycle));
-        } else if self.cycle_counter % CYCLE_LENGTH == 0 {
+            self.enemies.iter_mut().for_each(|e| e.mutate(self.cycle));
+            self.revive();
+        } else if self.cycle_counter.is_multiple_of(cycle_length) {
             // check if passed 60 seconds
             self.cycle = Cycle::Day;
             self.state = State::DayTransition;
             self.day += 1;
-            self.blasters.iter_mut().for_each(|b| b.mutate(self.cycle));
-            self.turrets.iter_mut().for_each(|b| b.mutate(self.cycle));
+            self.enemies.iter_mut().for_each(|e| e.mutate(self.cycle));
+            self.revive();
+        }
+        self.transition_counter = TRANSITION_LENGTH;
+    }
+
+    /// Brings back any downed players at the start of a day or night, as long
+    /// as someone is still flying.
+    fn revive(&mut self) {
+        if self.pilots.iter().any(|p| !p.player.dying()) {
+            self.pilots
+                .iter_mut()
+                .filter(|p| p.player.dead())
+                .for_each(|p| p.player.revive());
+        }
+    }
+
+    fn replaying(&self) -> bool {
+        self.pilots[0].controls.replaying()
+    }
+
+    /// Stops recording, then traces the recordings and adds the score to the
+    /// high score table, unless this was a replay.  Only solo runs go in the
+    /// table.
+    fn end_run(&mut self) {
+        let replaying = self.replaying();
+        self.recordings = self.pilots.iter_mut().filter_map(|p| p.controls.finish()).collect();
+        if !replaying {
+            for recording in self.recordings.iter().filter(|r| !r.truncated) {
+                recording.trace();
+            }
+        }
+        if !replaying && self.mode == Mode::Solo {
+            let mut save = Save::load();
+            let score = HighScore::new(self.score(), self.kills(), self.day, self.cycle);
+            self.high_score = save.high_scores_mut(self.difficulty.preset).insert(score);
+            if self.high_score.is_some() {
+                save.store();
+            }
         }
-        self.transition_counter = 119;
     }
 
+    /// Score for everyone together.
     fn score(&self) -> u32 {
-        self.time_alive / 10 + 10 * self.kills
+        self.time_alive / 10 + self.pilots.iter().map(|p| p.points).sum::<u32>()
     }
-    
-    /// Time in seconds
-    fn time(&self) -> u32 {
-        self.time_alive / 60
+
+    /// Score for player `idx` when keeping separate scores.
+    fn pilot_score(&self, idx: usize) -> u32 {
+        self.time_alive / 10 + self.pilots[idx].points
+    }
+
+    fn kills(&self) -> u32 {
+        self.pilots.iter().map(|p| p.kills).sum()
+    }
+
+    /// Everyone's stats for the run together.
+    fn stats(&self) -> Stats {
+        self.pilots.iter().fold(Stats::default(), |all, p| all.combine(&p.stats))
+    }
+
+    /// The longest combo anyone managed this run.
+    fn best_combo(&self) -> u32 {
+        self.pilots.iter().map(|p| p.combo.best()).max().unwrap_or(0)
     }
 
+    /// The player enemies go after, taking turns between everyone still
+    /// flying.
+    fn target(&self) -> usize {
+        let flying: Vec<usize> = (0..self.pilots.len())
+            .filter(|&i| !self.pilots[i].player.dying())
+            .collect();
+        match flying.len() {
+            0 => 0,
+            n => flying[self.frame as usize % n],
+        }
+    }
+
+    /// The player flying with the least health, who powerups are picked for.
+    fn neediest(&self) -> &Player {
+        self.pilots
+            .iter()
+            .map(|p| &p.player)
+            .filter(|p| !p.dying())
+            .min_by_key(|p| p.health())
+            .unwrap_or(&self.pilots[0].player)
+    }
+    
     /// Round, every 60 seconds
     fn round(&self) -> i32 {
-        self.time_alive as i32 / CYCLE_LENGTH as i32 + 1
+        self.time_alive as i32 / self.difficulty.cycle_length as i32 + 1
     }
 
     fn is_day(&self) -> bool {
         self.cycle == Cycle::Day
     }
 
-    fn is_night(&self) -> bool {
-        self.cycle == Cycle::Night
-    }
-
     fn draw(&mut self) {
         cloud::draw(self.frame, 1.4);
 
+        for pilot in &self.pilots {
+            if !pilot.player.dead() {
+                pilot.player.draw();
+            }
+        }
         color::set_draw(0x4320);
-        if !self.player.dead() {
-            self.player.draw();
+        self.enemies.iter().for_each(|e| e.draw());
+        if let Some(boss) = &self.boss {
+            boss.draw();
         }
-        self.blasters.iter().for_each(|e| e.draw());
-        self.turrets.iter().for_each(|e| e.draw());
         self.bullets.iter().for_each(|e| e.draw());
         self.enemy_bullets.iter().for_each(|e| e.draw());
         self.powerups.iter().for_each(|e| e.draw());
+        self.particles.draw();
 
-        // health
-        let heart = Sprite::heart.get();
-        for x in 0..self.player.health() {
-            heart.draw((x * 8 + 10) as i32, 150);
+        for (i, pilot) in self.pilots.iter().enumerate() {
+            draw_status(&pilot.player, i == 1);
         }
-
-        // speed boosts
-        let speedup = Sprite::speed.get();
-        for x in 0..self.player.total_speed_powerups() {
-            speedup.draw((x * 8 + 10) as i32, 140);
+        if let Some(boss) = self.boss.as_ref().filter(|b| !b.dying()) {
+            boss.draw_health_bar();
         }
     }
 
     fn cull_entities(&mut self) {
-        self.blasters = core::mem::take(&mut self.blasters)
-            .into_iter()
-            .filter(|b| !b.off_screen() && !b.dead())
-            .collect();
-
-        self.turrets = core::mem::take(&mut self.turrets)
+        self.enemies = core::mem::take(&mut self.enemies)
             .into_iter()
             .filter(|b| !b.off_screen() && !b.dead())
             .collect();
@@ -286,12 +709,24 @@ impl Game {
             .into_iter()
             .filter(|b| !b.off_screen() && !b.collected)
             .collect();
+
+        if self.boss.as_ref().is_some_and(|b| b.dead()) {
+            self.boss = None;
+        }
     }
 
     fn update(&mut self) {
-        self.player.update(self.frame);
-        self.blasters.iter_mut().for_each(|e| e.update(self.frame));
-        self.turrets.iter_mut().for_each(|e| e.update(self.frame));
+        for pilot in &mut self.pilots {
+            pilot.player.update(self.frame);
+            pilot.combo.tick();
+        }
+        if let Some(boss) = &mut self.boss {
+            boss.update(self.frame);
+        }
+        for enemy in &mut self.enemies {
+            enemy.update(self.frame);
+            enemy.aim_at(nearest_player(&self.pilots, enemy.pos()));
+        }
         self.bullets.iter_mut().for_each(|e| e.update(self.frame));
         self.enemy_bullets.iter_mut().for_each(|e| e.update(self.frame));
         self.powerups.iter_mut().for_each(|e| e.update(self.frame));
@@ -299,12 +734,31 @@ impl Game {
 
     fn resolve_collisions(&mut self) {
 
-        enemy_collisions(&mut self.player, &mut self.bullets, &mut self.blasters, &mut self.enemy_bullets, &mut self.kills);
-        enemy_collisions(&mut self.player, &mut self.bullets, &mut self.turrets, &mut self.enemy_bullets, &mut self.kills);
+        self.grid.rebuild(&self.bullets);
+        enemy_collisions(
+            &mut self.pilots,
+            &mut self.bullets,
+            &mut self.grid,
+            &mut self.enemies,
+            &mut self.enemy_bullets,
+            &mut self.particles,
+        );
+        if let Some(boss) = &mut self.boss {
+            boss_collisions(
+                boss,
+                &mut self.pilots,
+                &mut self.bullets,
+                &mut self.grid,
+                &mut self.enemy_bullets,
+                &mut self.powerups,
+                &mut self.particles,
+            );
+        }
 
 
-        for enemy in self.blasters.iter().filter(|e| e.dead()) {
-            let p = 0.15 - 0.03 * self.player.health() as f32;
+        for enemy in self.enemies.iter().filter(|e| e.dead()) {
+            let d = &self.difficulty;
+            let p = enemy.t.drop_chance() * d.drop_scale - d.drop_per_health * self.neediest().health() as f32;
             if self.is_day() && self.random.uniform_lt(p) {
                 let pos = enemy.pos();
                 let pt = self.get_power_type();
@@ -315,127 +769,256 @@ impl Game {
             }
         }
 
-        for enemy in self.turrets.iter().filter(|e| e.dead()) {
-            let p = 0.18 - 0.03 * self.player.health() as f32;
-            if self.is_day() && self.random.uniform_lt(p) {
-                let pos = enemy.pos();
-                let pt = self.get_power_type();
+        let flying: Vec<bool> = self.pilots.iter().map(|p| !p.player.dying()).collect();
 
-                if let Some(pt) = pt {
-                    self.powerups.push(PowerUp::spawn(pt, pos));
+        self.grid.rebuild(&self.enemy_bullets);
+        for pilot in self.pilots.iter_mut().zip(&flying).filter_map(|(p, &f)| f.then_some(p)) {
+            for i in self.grid.near(&pilot.player) {
+                let bullet = &mut self.enemy_bullets[i];
+                if pilot.player.collides_with(bullet) {
+                    pilot.hit(bullet.damage, &mut self.particles);
+                    bullet.dead = true;
                 }
             }
         }
 
-        if !self.player.dying() {
-            for bullet in &mut self.enemy_bullets {
-                if self.player.collides_with(bullet) {
-                    self.player.damage(bullet.damage);
-                    bullet.dead = true;
+        self.grid.rebuild(&self.powerups);
+        for pilot in self.pilots.iter_mut().zip(&flying).filter_map(|(p, &f)| f.then_some(p)) {
+            for i in self.grid.near(&pilot.player) {
+                let powerup = &mut self.powerups[i];
+                if !powerup.collected && pilot.player.collides_with(powerup) {
+                    pilot.player.power_up(powerup.t);
+                    pilot.stats.powerups += 1;
+                    powerup.collected = true;
+                    self.particles.burst(Burst::Pickup, powerup.pos);
                 }
             }
+        }
 
-            for powerup in &mut self.powerups {
-                if self.player.collides_with(powerup) {
-                    self.player.power_up(powerup.t);
-                    powerup.collected = true;
-                }
+        if self.pilots.iter().all(|p| p.player.dead()) {
+            if let State::Play = self.state {
+                self.end_run();
             }
-        } else if self.player.dead() {
             self.state = State::EndScreen;
         }
     }
 
     fn spawn_entities(&mut self) {
-        if self.spawn_cooldown <= 0 {
-            if self.round() >= 0 && self.turrets.len() < 3 && self.random.in_range(0, (10 - self.round()).max(3) as u32) < 1 {
-                let enemy = Turret::spawn(&mut self.random);
-                self.turrets.push(enemy);
-                self.new_spawn_cooldown();
-            } else {
-                let enemy = Blaster::spawn(&mut self.random, self.cycle, &self.player);
-                self.blasters.push(enemy);
-                self.new_spawn_cooldown();
+        let round = self.round() as u32;
+
+        if let State::Play = self.state {
+            for (kind, x) in wave::set_pieces(self.cycle, round, self.cycle_frame()) {
+                self.spawn_enemy(kind, x);
+            }
+            if self.cycle == Cycle::Night && self.cycle_frame() == wave::BOSS_FRAME {
+                self.boss = Some(Boss::new(self.day));
             }
         }
+
+        // the boss gets the sky to itself
+        if self.spawn_cooldown <= 0 && self.boss.is_none() {
+            let enemies = &self.enemies;
+            let on_screen = |kind| enemies.iter().filter(|e| e.t.kind() == kind).count();
+            if let Some(kind) = wave::pick(self.cycle, round, on_screen, &mut self.random) {
+                self.spawn_enemy(kind, None);
+            }
+            self.spawn_cooldown = self.difficulty.cooldown.frames(self.frame, self.cycle);
+        }
+    }
+
+    /// Spawns an enemy at `x`, or wherever its kind usually spawns.
+    fn spawn_enemy(&mut self, kind: EnemyKind, x: Option<f32>) {
+        let t = EnemyType::new(kind, &mut self.random);
+        let target = &self.pilots[self.target()].player;
+        let mut enemy = Enemy::spawn(t, &mut self.random, self.cycle, target, &self.difficulty);
+        if let Some(x) = x {
+            enemy.pos_mut().0 = x;
+        }
+        self.enemies.push(enemy);
+    }
+
+    /// Frames since the current cycle started, counting its transition.
+    fn cycle_frame(&self) -> u32 {
+        let frame = self.cycle_counter % self.difficulty.cycle_length;
+        match self.cycle {
+            Cycle::Day => frame,
+            Cycle::Night => frame - self.difficulty.night_start(),
+        }
     }
 
     fn get_power_type(&self) -> Option<PowerType> {
-        if self.round() >= 3 && self.player.speed < 1.5 {
-            Some(PowerType::Speed)
-        } else if self.round() >= 5 && self.player.speed < 2.0 {
+        let player = self.neediest();
+        if (self.round() >= 3 && player.speed < 1.5)
+            || (self.round() >= 5 && player.speed < 2.0)
+        {
             Some(PowerType::Speed)
-        } else if self.player.health < 5 {
+        } else if player.health < 3 {
+            Some(PowerType::Health)
+        } else if !player.spreading() {
+            Some(PowerType::Spreader)
+        } else if player.health < 5 {
             Some(PowerType::Health)
         } else {
             None
         }
     }
 
-    fn new_spawn_cooldown(&mut self) {
-        let cooldown = if self.frame > 300 * (100 - 30) {
-            30
-        } else {
-            100 - self.frame as i32 / 300
-        };
+    fn resolve_controls(&mut self) {
+        for (i, pilot) in self.pilots.iter_mut().enumerate() {
+            let player = &mut pilot.player;
+            let controls = &pilot.controls;
 
-        self.spawn_cooldown = match self.cycle {
-            Cycle::Day => cooldown,
-            Cycle::Night => cooldown / 4 * 3,
-        };
+            if controls.pressed_or_held(Button::Left) {
+                player.move_left();
+            } else if controls.pressed_or_held(Button::Right) {
+                player.move_right();
+            } else {
+                player.vel.0 = 0.0;
+            }
+
+            if controls.pressed_or_held(Button::Up) {
+                player.move_up();
+            } else if controls.pressed_or_held(Button::Down) {
+                player.move_down();
+            } else {
+                player.vel.1 = 0.0;
+            }
+
+            if controls.pressed(Button::Primary) {
+                let mut shots = player.shoot();
+                shots.iter_mut().for_each(|b| b.owner = i);
+                pilot.stats.shots += shots.len() as u32;
+                self.bullets.append(&mut shots);
+            }
+        }
     }
+}
+
+/// Position of the closest player still flying to `pos`.
+fn nearest_player(pilots: &[Pilot], pos: (f32, f32)) -> (f32, f32) {
+    let distance = |p: &Player| (p.pos.0 - pos.0).powi(2) + (p.pos.1 - pos.1).powi(2);
+    pilots
+        .iter()
+        .map(|p| &p.player)
+        .filter(|p| !p.dying())
+        .min_by(|a, b| distance(a).total_cmp(&distance(b)))
+        .unwrap_or(&pilots[0].player)
+        .pos
+}
 
+/// Hearts, speed boosts and spreader time left for a player along the bottom
+/// left, or mirrored along the bottom right.
+fn draw_status(player: &Player, right: bool) {
+    let x = |offset: i32, width: i32| if right { 160 - offset - width } else { offset };
 
-    fn resolve_controls(&mut self) {
-        let mut player = &mut self.player;
-        let controls = &mut self.controls;
+    color::set_draw(0x4320);
+    let heart = Sprite::heart.get();
+    for i in 0..player.health() as i32 {
+        heart.draw(x(i * 8 + 10, heart.width as i32), 150);
+    }
 
-        if controls.pressed_or_held(Button::Left) {
-            player.move_left();
-        } else if controls.pressed_or_held(Button::Right) {
-            player.move_right();
-        } else {
-            player.vel.0 = 0.0;
-        }
+    let speedup = Sprite::speed.get();
+    for i in 0..player.total_speed_powerups() as i32 {
+        speedup.draw(x(i * 8 + 10, speedup.width as i32), 140);
+    }
 
-        if controls.pressed_or_held(Button::Up) {
-            player.move_up();
-        } else if controls.pressed_or_held(Button::Down) {
-            player.move_down();
-        } else {
-            player.vel.1 = 0.0;
+    if player.spreading() {
+        let spreader = Sprite::spreader.get();
+        spreader.draw(x(10, spreader.width as i32), 130);
+        color::set_draw(0x03);
+        let width = (player.spreader_left() * 30.0).ceil() as i32;
+        rect(x(20, width), 132, width as u32, 4);
+    }
+}
+
+/// Like `enemy_collisions`, except flying into the boss only hurts the player,
+/// and beating it leaves a heart behind.
+fn boss_collisions(
+    boss: &mut Boss,
+    pilots: &mut [Pilot],
+    bullets: &mut [Bullet],
+    grid: &mut Grid,
+    enemy_bullets: &mut Vec<Bullet>,
+    powerups: &mut Vec<PowerUp>,
+    particles: &mut Particles,
+) {
+    enemy_bullets.append(&mut boss.shoot());
+
+    if boss.dying() {
+        return;
+    }
+
+    for i in grid.near(boss) {
+        let bullet = &mut bullets[i];
+        if !bullet.dead && boss.collides_with(bullet) {
+            boss.damage(bullet.damage);
+            bullet.dead = true;
+            pilots[bullet.owner].stats.hits += 1;
+            particles.burst(Burst::Impact, bullet.pos);
+            if boss.dying() {
+                pilots[bullet.owner].kill();
+                pilots[bullet.owner].stats.bosses += 1;
+                camera::shake(40);
+                camera::freeze(12);
+                particles.burst(Burst::BossExplosion, boss.pos());
+                powerups.push(PowerUp::spawn(PowerType::Health, boss.pos()));
+                break;
+            }
         }
+    }
 
-        if controls.pressed(Button::Primary) {
-            self.bullets.append(&mut player.shoot());
+    for pilot in pilots.iter_mut() {
+        if !pilot.player.dying() && boss.collides_with(&pilot.player) {
+            pilot.hit(1, particles);
         }
     }
 }
 
-fn enemy_collisions<T>(
-    player: &mut Player, 
-    bullets: &mut Vec<Bullet>, 
-    enemies: &mut Vec<T>, 
+/// Enemies shoot and are hit by `bullets`, which `grid` has been built from,
+/// and run into players.
+fn enemy_collisions(
+    pilots: &mut [Pilot],
+    bullets: &mut [Bullet], 
+    grid: &mut Grid,
+    enemies: &mut [Enemy], 
     enemy_bullets: &mut Vec<Bullet>,
-    kills: &mut u32,
-) where T: Movement + Render + Alive + Shoot {
+    particles: &mut Particles,
+) {
     for enemy in enemies {
         enemy_bullets.append(&mut enemy.shoot());
 
         // ensure that bullets pass through dying enemies
         if !enemy.dying() {
-            for bullet in bullets.iter_mut() {
+            for i in grid.near(enemy) {
+                let bullet = &mut bullets[i];
                 if enemy.collides_with(bullet) {
                     enemy.damage(bullet.damage);
-                    if enemy.dying() { *kills += 1 }
+                    let pilot = &mut pilots[bullet.owner];
+                    if !bullet.dead {
+                        pilot.stats.hits += 1;
+                        particles.burst(Burst::Impact, bullet.pos);
+                    }
+                    if enemy.dying() {
+                        pilot.kill();
+                        pilot.stats.add_kill(enemy.t.kind());
+                        camera::shake(6);
+                        particles.burst(Burst::Explosion, enemy.pos());
+                        if enemy.t.kind() == EnemyKind::Turret {
+                            camera::freeze(4);
+                        }
+                    }
                     bullet.dead = true;
                 }
             }
         }
 
-        if !player.dying() && enemy.collides_with(player) {
-            enemy.kill();
-            player.damage(1);
+        for pilot in pilots.iter_mut() {
+            // an enemy already blowing up can't hit anyone, or burst again
+            if !enemy.dying() && !pilot.player.dying() && enemy.collides_with(&pilot.player) {
+                enemy.kill();
+                pilot.hit(1, particles);
+                particles.burst(Burst::Explosion, enemy.pos());
+            }
         }
     }
 }
@@ -0,0 +1,160 @@
+use crate::platform::*;
+use crate::color;
+use crate::format;
+use crate::Cycle;
+
+/// Number of scores kept in the table.
+pub const TABLE_SIZE: usize = 10;
+
+/// Bytes each score takes up on disk.
+const ENTRY_SIZE: usize = 8;
+
+/// How a single run ended.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct HighScore {
+    pub score: u32,
+    pub kills: u16,
+    pub day: u8,
+    pub night: bool,
+}
+
+impl HighScore {
+    pub fn new(score: u32, kills: u32, day: u32, cycle: Cycle) -> Self {
+        Self {
+            score,
+            kills: kills.min(u16::MAX as u32) as u16,
+            day: day.min(u8::MAX as u32) as u8,
+            night: cycle == Cycle::Night,
+        }
+    }
+
+    fn to_bytes(self) -> [u8; ENTRY_SIZE] {
+        let s = self.score.to_le_bytes();
+        let k = self.kills.to_le_bytes();
+        [s[0], s[1], s[2], s[3], k[0], k[1], self.day, self.night as u8]
+    }
+
+    fn from_bytes(b: &[u8]) -> Self {
+        Self {
+            score: u32::from_le_bytes([b[0], b[1], b[2], b[3]]),
+            kills: u16::from_le_bytes([b[4], b[5]]),
+            day: b[6],
+            night: b[7] != 0,
+        }
+    }
+}
+
+/// The best runs, highest score first.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct HighScores {
+    scores: Vec<HighScore>,
+}
+
+impl HighScores {
+    pub fn scores(&self) -> &[HighScore] {
+        &self.scores
+    }
+
+    /// Adds a score to the table, returning its rank from 0 if it made the cut.
+    /// Ties go below the scores already in the table.
+    pub fn insert(&mut self, score: HighScore) -> Option<usize> {
+        if score.score == 0 {
+            return None;
+        }
+        let rank = self.scores.iter().position(|s| s.score < score.score)
+            .unwrap_or(self.scores.len());
+        if rank >= TABLE_SIZE {
+            return None;
+        }
+        self.scores.insert(rank, score);
+        self.scores.truncate(TABLE_SIZE);
+        Some(rank)
+    }
+
+    /// Number of bytes `to_bytes` writes.
+    pub fn byte_len(&self) -> usize {
+        1 + self.scores.len() * ENTRY_SIZE
+    }
+
+    /// A count byte followed by each score.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![self.scores.len() as u8];
+        for score in &self.scores {
+            bytes.extend_from_slice(&score.to_bytes());
+        }
+        bytes
+    }
+
+    /// Reads a table written by `to_bytes` from the front of `bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let count = *bytes.first()? as usize;
+        if count > TABLE_SIZE || bytes.len() < 1 + count * ENTRY_SIZE {
+            return None;
+        }
+        let scores = bytes[1..1 + count * ENTRY_SIZE]
+            .chunks(ENTRY_SIZE)
+            .map(HighScore::from_bytes)
+            .collect();
+        Some(Self { scores })
+    }
+
+    /// Draws the table starting at height `y`, with the score at rank
+    /// `highlight` in a different color.
+    pub fn draw(&self, y: i32, highlight: Option<usize>) {
+        if self.scores.is_empty() {
+            color::set_draw(0x03);
+            text("No scores yet", 28, y);
+            return;
+        }
+
+        for (i, s) in self.scores.iter().enumerate() {
+            let cycle = if s.night { "N" } else { "D" };
+            let row = y + i as i32 * 10;
+            color::set_draw(if highlight == Some(i) { 0x02 } else { 0x03 });
+            text(format::padded(i as u32 + 1, 2).str("."), 4, row);
+            text(format::padded(s.score, 6), 32, row);
+            text(format::Line::new().str(cycle).num(s.day as u32), 88, row);
+            text(format::padded(s.kills as u32, 3).str("k"), 120, row);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(score: u32) -> HighScore {
+        HighScore::new(score, 0, 1, Cycle::Day)
+    }
+
+    #[test]
+    fn ranks_highest_first_and_keeps_the_best() {
+        let mut table = HighScores::default();
+        assert_eq!(table.insert(score(50)), Some(0));
+        assert_eq!(table.insert(score(80)), Some(0));
+        // ties go below
+        assert_eq!(table.insert(score(50)), Some(2));
+        assert_eq!(table.insert(score(0)), None);
+
+        for s in 100..120 {
+            table.insert(score(s));
+        }
+        assert_eq!(table.scores().len(), TABLE_SIZE);
+        assert_eq!(table.scores()[0].score, 119);
+        assert_eq!(table.insert(score(50)), None);
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut table = HighScores::default();
+        table.insert(HighScore::new(1234, 70000, 300, Cycle::Night));
+        table.insert(score(9));
+
+        let bytes = table.to_bytes();
+        assert_eq!(bytes.len(), table.byte_len());
+        let loaded = HighScores::from_bytes(&bytes).unwrap();
+        assert_eq!(loaded, table);
+        assert_eq!(loaded.scores()[0], HighScore { score: 1234, kills: u16::MAX, day: u8::MAX, night: true });
+        assert!(HighScores::from_bytes(&bytes[..bytes.len() - 1]).is_none());
+    }
+}
@@ -1,11 +1,13 @@
-#[cfg(feature = "buddy-alloc")]
+#[cfg(all(feature = "buddy-alloc", target_arch = "wasm32"))]
 mod alloc;
 mod wasm4;
-use wasm4::sys::*;
 pub use wasm4::*;
+pub mod platform;
+use platform::*;
 
 mod util;
-use util::Random;
+mod format;
+pub use util::Random;
 mod entity;
 pub use entity::*;
 
@@ -16,12 +18,27 @@ mod sound;
 mod color;
 use color::*;
 mod cloud;
+mod wave;
+mod combo;
+mod stats;
+mod achievement;
+mod camera;
+mod particle;
+mod difficulty;
+pub use difficulty::*;
+pub mod replay;
+mod highscore;
+pub use highscore::*;
+mod save;
+mod options;
+mod menu;
+use menu::{Menu, MenuAction};
 mod game;
 pub use game::*;
 
 enum GameState {
-    Menu,
-    Playing(Game),
+    Menu(Menu),
+    Playing(Box<Game>),
 }
 
 struct App {
@@ -35,14 +52,13 @@ impl App {
         Self {
             controls: Controls::new(),
             frame: 0,
-            state: GameState::Menu,
+            state: GameState::Menu(Menu::new()),
         }
     }
 }
 
 impl Runtime for App {
     fn start() -> Self {
-        color::Palette::Day.set();
         App::new()
     }
 
@@ -50,34 +66,19 @@ impl Runtime for App {
         self.controls.next();
         self.frame += 1;
 
-        use GameState::*;
         match &mut self.state {
-            Menu => menu_update(self),
-            Playing(game) => game.tick(),
+            GameState::Menu(menu) => {
+                if let Some(MenuAction::Start(mode, difficulty)) = menu.update(&self.controls, self.frame) {
+                    self.state = GameState::Playing(Box::new(Game::new(Random::seed(self.frame), mode, difficulty)));
+                }
+            }
+            GameState::Playing(game) => {
+                if let Some(GameAction::Quit) = game.tick(&self.controls) {
+                    self.state = GameState::Menu(Menu::new());
+                }
+            }
         }
     }
 }
 
-fn menu_update(game: &mut App) {
-    // draw clouds using perlin noise cause why not
-    cloud::draw(game.frame, -1.0);
-
-    color::set_draw(0x03);
-    text("Fool's Paradise", 10, 10);
-    text("Start", 10, 130);
-    text("v0.2.0", 160-6*8, 152);
-
-    let s = Sprite::enemy1.get();
-
-    color::set_draw(0x4320);
-    for (x, y) in [(20, 50), (100, 70), (130, 55), (55, 40), (45, 85)] {
-        blit(&s.data, x, y, s.width, s.height, s.flags);
-    }
-
-    if game.controls.pressed(Button::Primary) {
-        game.controls.next();  // stop from firing first shot in game
-        game.state = GameState::Playing(Game::new(Random::seed(game.frame)));
-    }
-}
-
 main! { App }
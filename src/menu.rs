@@ -1,22 +1,397 @@
-enum MenuState {
+use crate::platform::*;
+use crate::save::Save;
+use crate::achievement;
+use crate::camera;
+use crate::{color, cloud, Button, Controls, Difficulty, Level, Mode, Preset, Sprite};
+
+/// A page of the menu.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MenuState {
     Main,
+    CoOp,
     Options,
+    Custom,
+    HighScores,
+    Achievements,
+    Credits,
 }
 
-enum MenuItem {
+/// Something on a page that can be selected.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MenuItem {
     Start,
+    CoOp,
+    Shared,
+    Separate,
     Options,
+    HighScores,
+    Achievements,
+    Credits,
     Sound,
+    Theme,
+    Shake,
+    Difficulty,
+    Table,
+    Custom,
+    Health,
+    Spawns,
+    Drops,
+    Cycle,
+    Back,
+}
+
+/// What the menu wants the app to do after an update.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MenuAction {
+    Start(Mode, Difficulty),
 }
 
 impl MenuState {
-    fn list(&self) -> Vec<&'static str> {
-        use MenuState::*;
+    fn items(&self) -> &'static [MenuItem] {
+        use MenuItem::*;
         match self {
-            Main => vec![
-                "Start",
-                "Options",
-            ];
+            MenuState::Main => &[Start, CoOp, Options, HighScores, Achievements, Credits],
+            MenuState::CoOp => &[Shared, Separate, Back],
+            MenuState::Options => &[Sound, Theme, Shake, Difficulty, Custom, Back],
+            MenuState::Custom => &[Health, Spawns, Drops, Cycle, Back],
+            MenuState::HighScores => &[Table, Back],
+            MenuState::Achievements => &[Back],
+            MenuState::Credits => &[Back],
+        }
+    }
+
+    fn title(&self) -> &'static str {
+        match self {
+            MenuState::Main => "Fool's Paradise",
+            MenuState::CoOp => "Co-op",
+            MenuState::Options => "Options",
+            MenuState::Custom => "Custom",
+            MenuState::HighScores => "High Scores",
+            MenuState::Achievements => "Achievements",
+            MenuState::Credits => "Credits",
+        }
+    }
+}
+
+/// The menu pages, the cursor on each, and the save they edit.
+pub struct Menu {
+    state: MenuState,
+    cursor: usize,
+    /// Pages to go back to, and where their cursor was.
+    parents: Vec<(MenuState, usize)>,
+    save: Save,
+    /// The high score table being shown, which starts at the current preset
+    /// but never changes it.
+    table: Preset,
+}
+
+impl Menu {
+    pub fn new() -> Self {
+        let save = Save::load();
+        save.options.apply();
+        color::Palette::Day.set();
+        Self {
+            state: MenuState::Main,
+            cursor: 0,
+            parents: Vec::new(),
+            table: save.options.preset,
+            save,
+        }
+    }
+
+    /// Handles input and draws the current page.
+    pub fn update(&mut self, controls: &Controls, frame: u32) -> Option<MenuAction> {
+        let items = self.state.items();
+
+        if controls.pressed(Button::Up) {
+            self.cursor = (self.cursor + items.len() - 1) % items.len();
+        } else if controls.pressed(Button::Down) {
+            self.cursor = (self.cursor + 1) % items.len();
+        }
+
+        let mut action = None;
+        if controls.pressed(Button::Primary) {
+            action = self.select(items[self.cursor]);
+        } else if controls.pressed(Button::Secondary) {
+            self.back();
+        }
+
+        self.draw(frame);
+        action
+    }
+
+    fn select(&mut self, item: MenuItem) -> Option<MenuAction> {
+        match item {
+            MenuItem::Start => return Some(self.start(Mode::Solo)),
+            MenuItem::CoOp => self.open(MenuState::CoOp),
+            MenuItem::Shared => return Some(self.start(Mode::Shared)),
+            MenuItem::Separate => return Some(self.start(Mode::Separate)),
+            MenuItem::Options => self.open(MenuState::Options),
+            MenuItem::HighScores => {
+                // reload in case a run just ended
+                self.save.high_scores = Save::load().high_scores;
+                self.table = self.save.options.preset;
+                self.open(MenuState::HighScores);
+            }
+            MenuItem::Achievements => {
+                self.save.achievements = Save::load().achievements;
+                self.open(MenuState::Achievements);
+            }
+            MenuItem::Credits => self.open(MenuState::Credits),
+            MenuItem::Sound => {
+                self.save.options.sound = !self.save.options.sound;
+                self.save_options();
+            }
+            MenuItem::Theme => {
+                self.save.options.theme = self.save.options.theme.next();
+                self.save_options();
+            }
+            MenuItem::Shake => {
+                self.save.options.shake = self.save.options.shake.next();
+                self.save_options();
+            }
+            MenuItem::Difficulty => {
+                self.save.options.preset = self.save.options.preset.next();
+                self.save_options();
+            }
+            MenuItem::Table => self.table = self.table.next(),
+            MenuItem::Custom => self.open(MenuState::Custom),
+            MenuItem::Health | MenuItem::Spawns | MenuItem::Drops | MenuItem::Cycle => {
+                let custom = &mut self.save.options.custom;
+                let knob = match item {
+                    MenuItem::Health => &mut custom.health,
+                    MenuItem::Spawns => &mut custom.spawns,
+                    MenuItem::Drops => &mut custom.drops,
+                    _ => &mut custom.cycle,
+                };
+                *knob = knob.next();
+                // changing a knob only makes sense when playing with them
+                self.save.options.preset = Preset::Custom;
+                self.save_options();
+            }
+            MenuItem::Back => self.back(),
+        }
+        None
+    }
+
+    fn start(&self, mode: Mode) -> MenuAction {
+        MenuAction::Start(mode, self.save.options.difficulty())
+    }
+
+    fn open(&mut self, state: MenuState) {
+        self.parents.push((self.state, self.cursor));
+        self.state = state;
+        self.cursor = 0;
+    }
+
+    fn back(&mut self) {
+        if let Some((state, cursor)) = self.parents.pop() {
+            self.state = state;
+            self.cursor = cursor;
+        }
+    }
+
+    /// Applies the options and writes them to disk, leaving everything else
+    /// on disk as it is.
+    fn save_options(&mut self) {
+        self.save.options.apply();
+        color::Palette::Day.set();
+        let mut save = Save::load();
+        save.options = self.save.options.clone();
+        save.store();
+    }
+
+    fn label(&self, item: MenuItem) -> &'static str {
+        use MenuItem::*;
+        match item {
+            Start => "Start",
+            CoOp => "Co-op",
+            Shared => "Shared score",
+            Separate => "Separate scores",
+            Options => "Options",
+            HighScores => "High Scores",
+            Achievements => "Achievements",
+            Credits => "Credits",
+            Sound => if self.save.options.sound { "Sound   On" } else { "Sound   Off" },
+            Theme => match self.save.options.theme {
+                color::Theme::Classic => "Theme   Classic",
+                color::Theme::Contrast => "Theme   Contrast",
+                color::Theme::Colorsafe => "Theme   Colorsafe",
+                color::Theme::Greyscale => "Theme   Greyscale",
+            },
+            Shake => match self.save.options.shake {
+                camera::Shake::Off => "Shake   Off",
+                camera::Shake::Low => "Shake   Low",
+                camera::Shake::Full => "Shake   Full",
+            },
+            Difficulty => match self.save.options.preset {
+                Preset::Easy => "Difficulty Easy",
+                Preset::Normal => "Difficulty Normal",
+                Preset::Hard => "Difficulty Hard",
+                Preset::Custom => "Difficulty Custom",
+            },
+            Table => match self.table {
+                Preset::Easy => "Table   Easy",
+                Preset::Normal => "Table   Normal",
+                Preset::Hard => "Table   Hard",
+                Preset::Custom => "Table   Custom",
+            },
+            Custom => "Custom",
+            Health => match self.save.options.custom.health {
+                Level::Easy => "Health  Easy",
+                Level::Normal => "Health  Normal",
+                Level::Hard => "Health  Hard",
+            },
+            Spawns => match self.save.options.custom.spawns {
+                Level::Easy => "Spawns  Easy",
+                Level::Normal => "Spawns  Normal",
+                Level::Hard => "Spawns  Hard",
+            },
+            Drops => match self.save.options.custom.drops {
+                Level::Easy => "Drops   Easy",
+                Level::Normal => "Drops   Normal",
+                Level::Hard => "Drops   Hard",
+            },
+            Cycle => match self.save.options.custom.cycle {
+                Level::Easy => "Days    Easy",
+                Level::Normal => "Days    Normal",
+                Level::Hard => "Days    Hard",
+            },
+            Back => "Back",
+        }
+    }
+
+    fn draw(&self, frame: u32) {
+        // draw clouds using perlin noise cause why not
+        cloud::draw(frame, -1.0);
+
+        color::set_draw(0x03);
+        text(self.state.title(), 10, 10);
+
+        match self.state {
+            MenuState::Main => {
+                let s = Sprite::enemy1.get();
+                color::set_draw(0x4320);
+                for (x, y) in [(20, 50), (100, 70), (130, 55), (55, 40), (45, 75)] {
+                    blit(s.data, x, y, s.width, s.height, s.flags);
+                }
+                color::set_draw(0x03);
+                text("v0.2.0", 160-6*8, 152);
+            }
+            MenuState::HighScores => self.save.high_scores(self.table).draw(26, None),
+            MenuState::Achievements => achievement::draw_list(self.save.achievements, 30),
+            MenuState::Credits => {
+                color::set_draw(0x03);
+                text("A game by rutrum", 10, 30);
+                text("for the WASM-4", 10, 40);
+                text("Game Jam 2022.", 10, 50);
+                text("Palettes from", 10, 70);
+                text("lospec.com", 10, 80);
+            }
+            MenuState::CoOp => {
+                color::set_draw(0x03);
+                text("Player 2 flies", 10, 30);
+                text("with gamepad 2,", 10, 40);
+                text("or joins over", 10, 50);
+                text("netplay.", 10, 60);
+                text("Downed players", 10, 75);
+                text("return at the", 10, 85);
+                text("next day or night.", 10, 95);
+            }
+            MenuState::Options => {}
+            MenuState::Custom => {
+                color::set_draw(0x03);
+                text("Set each part of", 10, 30);
+                text("the custom", 10, 40);
+                text("difficulty.", 10, 50);
+            }
+        }
+
+        let labels: Vec<&str> = self.state.items().iter().map(|&i| self.label(i)).collect();
+        let y = 150 - 10 * labels.len() as i32;
+        draw_items(&labels, self.cursor, 10, y);
+    }
+}
+
+impl Default for Menu {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Draws a list of items 10 pixels apart starting at (x, y), with an arrow
+/// next to the one under the cursor.
+pub fn draw_items(labels: &[&str], cursor: usize, x: i32, y: i32) {
+    for (i, label) in labels.iter().enumerate() {
+        let row = y + i as i32 * 10;
+        if i == cursor {
+            color::set_draw(0x2000);
+            Sprite::arrow.get().draw(x, row);
+            color::set_draw(0x02);
+        } else {
+            color::set_draw(0x03);
+        }
+        text(label, x + 10, row);
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use crate::platform::{headless, BUTTON_1, BUTTON_2, BUTTON_DOWN, BUTTON_UP};
+
+    /// Taps `button` on the first gamepad, returning what the menu asked for.
+    fn tap(menu: &mut Menu, controls: &mut Controls, button: u8) -> Option<MenuAction> {
+        let mut action = None;
+        for gamepad in [button, 0] {
+            headless::with(|h| {
+                h.next_frame();
+                h.gamepads[0] = gamepad;
+            });
+            controls.next();
+            action = action.or(menu.update(controls, 0));
+        }
+        action
+    }
+
+    #[test]
+    fn pages_open_and_close() {
+        let mut menu = Menu::new();
+        let mut controls = Controls::new();
+
+        for button in [BUTTON_DOWN, BUTTON_DOWN, BUTTON_DOWN, BUTTON_1] {
+            tap(&mut menu, &mut controls, button);
+        }
+        assert_eq!(menu.state, MenuState::HighScores);
+
+        // back goes to the item the page was opened from
+        tap(&mut menu, &mut controls, BUTTON_2);
+        assert_eq!((menu.state, menu.cursor), (MenuState::Main, 3));
+
+        for button in [BUTTON_UP, BUTTON_1, BUTTON_1] {
+            tap(&mut menu, &mut controls, button);
+        }
+        assert_eq!(menu.state, MenuState::Options);
+        assert!(!Save::load().options.sound);
+
+        for button in [BUTTON_2, BUTTON_UP, BUTTON_1, BUTTON_DOWN] {
+            tap(&mut menu, &mut controls, button);
+        }
+        let start = tap(&mut menu, &mut controls, BUTTON_1);
+        assert_eq!(start, Some(MenuAction::Start(Mode::Separate, Difficulty::default())));
+    }
+
+    #[test]
+    fn browsing_tables_leaves_the_preset() {
+        let mut menu = Menu::new();
+        menu.select(MenuItem::HighScores);
+        let disk = headless::with(|h| h.disk.clone());
+
+        for preset in [Preset::Hard, Preset::Custom, Preset::Easy, Preset::Normal] {
+            menu.select(MenuItem::Table);
+            assert_eq!(menu.table, preset);
+            assert_eq!(menu.save.options.preset, Preset::Normal);
         }
+        assert_eq!(headless::with(|h| h.disk.clone()), disk);
     }
 }
@@ -0,0 +1,99 @@
+use crate::sound;
+use crate::camera::{self, Shake};
+use crate::color::{self, Theme};
+use crate::{Custom, Difficulty, Preset};
+
+/// Settings picked in the options menu, kept on disk.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Options {
+    pub sound: bool,
+    pub theme: Theme,
+    /// Screen shake and hit-stop.
+    pub shake: Shake,
+    pub preset: Preset,
+    /// Knobs for the custom preset, kept when another preset is picked.
+    pub custom: Custom,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            sound: true,
+            theme: Theme::default(),
+            shake: Shake::default(),
+            preset: Preset::default(),
+            custom: Custom::default(),
+        }
+    }
+}
+
+impl Options {
+    /// Makes the rest of the game follow these options.
+    pub fn apply(&self) {
+        sound::set_enabled(self.sound);
+        color::set_theme(self.theme);
+        camera::set_level(self.shake);
+    }
+
+    /// The difficulty runs are played at.
+    pub fn difficulty(&self) -> Difficulty {
+        Difficulty::new(self.preset, self.custom)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![self.sound as u8, self.theme as u8, self.preset as u8];
+        bytes.extend(self.custom.to_bytes());
+        bytes.push(self.shake as u8);
+        bytes
+    }
+
+    /// Reads options written by `to_bytes`.  Anything missing from the end
+    /// keeps its default, so older saves still load.
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        let mut options = Self::default();
+        if let Some(&b) = bytes.first() {
+            options.sound = b != 0;
+        }
+        if let Some(&b) = bytes.get(1) {
+            options.theme = Theme::from_byte(b);
+        }
+        if let Some(&b) = bytes.get(2) {
+            options.preset = Preset::from_byte(b);
+        }
+        if let Some(&custom) = bytes.get(3..7).and_then(|b| b.first_chunk()) {
+            options.custom = Custom::from_bytes(custom);
+        }
+        if let Some(&b) = bytes.get(7) {
+            options.shake = Shake::from_byte(b);
+        }
+        options
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Level;
+
+    #[test]
+    fn round_trip() {
+        let options = Options {
+            sound: false,
+            theme: Theme::Greyscale,
+            shake: Shake::Low,
+            preset: Preset::Custom,
+            custom: Custom { health: Level::Hard, ..Default::default() },
+        };
+        assert_eq!(Options::from_bytes(&options.to_bytes()), options);
+    }
+
+    #[test]
+    fn older_saves_keep_the_defaults() {
+        let options = Options::from_bytes(&[0, Theme::Contrast as u8]);
+        assert!(!options.sound);
+        assert_eq!(options.theme, Theme::Contrast);
+        assert_eq!(options.preset, Preset::default());
+        assert_eq!(options.shake, Shake::default());
+        assert_eq!(Options::from_bytes(&[]), Options::default());
+    }
+}
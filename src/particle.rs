@@ -0,0 +1,240 @@
+//! Pixels and tiny shards thrown out for effect when things blow up, get hit
+//! or are picked up.  Particles live in a fixed pool, so a busy screen
+//! recycles the oldest instead of allocating more, and they roll their own
+//! `Random` so they never change how a run plays out.
+
+use crate::platform::*;
+use crate::{camera, color, Random};
+
+/// Most particles alive at once.
+const POOL_SIZE: usize = 64;
+
+/// Kept of a particle's velocity from one frame to the next.
+const DRAG: f32 = 0.96;
+
+/// Frames before the end that a particle starts to flicker out.
+const FADE: u16 = 8;
+
+/// 4x4 1BPP shards of debris.
+const SHARDS: [[u8; 2]; 3] = [
+    [0b0100_1110, 0b0110_0000],
+    [0b1000_1100, 0b0111_0010],
+    [0b0110_1100, 0b1000_0000],
+];
+
+/// Blit flags for each quarter turn, so shards tumble as they fly.
+const TURNS: [u32; 4] = [
+    0,
+    BLIT_ROTATE,
+    BLIT_FLIP_X | BLIT_FLIP_Y,
+    BLIT_ROTATE | BLIT_FLIP_X | BLIT_FLIP_Y,
+];
+
+/// Something that throws out a handful of particles.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Burst {
+    /// An enemy shot down or flown into.
+    Explosion,
+    /// The boss going down.
+    BossExplosion,
+    /// A bullet landing.
+    Impact,
+    /// A player losing health.
+    Hurt,
+    /// A powerup being collected.
+    Pickup,
+}
+
+impl Burst {
+    /// Pixels and shards thrown out.
+    fn counts(self) -> (u32, u32) {
+        match self {
+            Burst::Explosion => (10, 3),
+            Burst::BossExplosion => (24, 8),
+            Burst::Impact => (3, 0),
+            Burst::Hurt => (8, 0),
+            Burst::Pickup => (8, 0),
+        }
+    }
+
+    /// Fastest a particle is thrown, in pixels per frame.
+    fn speed(self) -> f32 {
+        match self {
+            Burst::Explosion => 1.2,
+            Burst::BossExplosion => 2.0,
+            Burst::Impact => 0.8,
+            Burst::Hurt => 1.0,
+            Burst::Pickup => 0.6,
+        }
+    }
+
+    /// Added to a particle's downwards speed every frame.  Pickups float up.
+    fn gravity(self) -> f32 {
+        match self {
+            Burst::Explosion => 0.04,
+            Burst::BossExplosion => 0.03,
+            Burst::Impact => 0.0,
+            Burst::Hurt => 0.05,
+            Burst::Pickup => -0.03,
+        }
+    }
+
+    /// Longest a particle lasts, in frames.  Each lasts somewhere between
+    /// half of this and all of it.
+    fn life(self) -> u32 {
+        match self {
+            Burst::Explosion => 30,
+            Burst::BossExplosion => 60,
+            Burst::Impact => 8,
+            Burst::Hurt => 20,
+            Burst::Pickup => 30,
+        }
+    }
+
+    /// Draw colours particles are picked from.
+    fn colors(self) -> &'static [u16] {
+        match self {
+            Burst::Explosion | Burst::BossExplosion => &[0x2, 0x3, 0x4],
+            Burst::Impact => &[0x3, 0x4],
+            Burst::Hurt => &[0x2, 0x3],
+            Burst::Pickup => &[0x2, 0x4],
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+enum Look {
+    #[default]
+    Pixel,
+    /// An index into `SHARDS`.
+    Shard(usize),
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Particle {
+    pos: (f32, f32),
+    vel: (f32, f32),
+    gravity: f32,
+    /// Frames left, with the slot free for reuse at zero.
+    life: u16,
+    /// The draw colour.
+    color: u16,
+    look: Look,
+}
+
+pub struct Particles {
+    pool: [Particle; POOL_SIZE],
+    /// Where to start looking for a free slot, which is also the oldest
+    /// particle when there are none.
+    next: usize,
+    random: Random,
+}
+
+impl Particles {
+    pub fn new(seed: u32) -> Self {
+        Self {
+            pool: [Particle::default(); POOL_SIZE],
+            next: 0,
+            random: Random::seed(seed),
+        }
+    }
+
+    /// Throws out a `burst` of particles from `pos`.
+    pub fn burst(&mut self, burst: Burst, pos: (f32, f32)) {
+        let (pixels, shards) = burst.counts();
+        let colors = burst.colors();
+        for i in 0..pixels + shards {
+            let angle = self.random.angle();
+            let speed = burst.speed() * (0.25 + 0.75 * self.random.float());
+            let life = self.random.in_range(burst.life() / 2, burst.life() + 1);
+            let color = colors[self.random.in_range(0, colors.len() as u32) as usize];
+            let look = if i < pixels {
+                Look::Pixel
+            } else {
+                Look::Shard(self.random.in_range(0, SHARDS.len() as u32) as usize)
+            };
+            self.emit(Particle {
+                pos,
+                vel: (speed * angle.sin(), speed * angle.cos()),
+                gravity: burst.gravity(),
+                life: life as u16,
+                color,
+                look,
+            });
+        }
+    }
+
+    /// Puts `particle` in the first free slot, or over the oldest if the
+    /// pool is full.
+    fn emit(&mut self, particle: Particle) {
+        let slot = (0..POOL_SIZE)
+            .map(|i| (self.next + i) % POOL_SIZE)
+            .find(|&i| self.pool[i].life == 0)
+            .unwrap_or(self.next);
+        self.pool[slot] = particle;
+        self.next = (slot + 1) % POOL_SIZE;
+    }
+
+    /// Moves every particle along a frame, letting them age.
+    pub fn update(&mut self) {
+        for particle in self.pool.iter_mut().filter(|p| p.life > 0) {
+            particle.vel.0 *= DRAG;
+            particle.vel.1 = particle.vel.1 * DRAG + particle.gravity;
+            particle.pos.0 += particle.vel.0;
+            particle.pos.1 += particle.vel.1;
+            particle.life -= 1;
+        }
+    }
+
+    pub fn draw(&self) {
+        let (dx, dy) = camera::offset();
+        for particle in self.pool.iter().filter(|p| p.life > 0) {
+            if particle.life < FADE && particle.life % 2 == 0 {
+                continue;
+            }
+            let x = particle.pos.0 as i32 + dx;
+            let y = particle.pos.1 as i32 + dy;
+            match particle.look {
+                Look::Pixel => {
+                    color::set_draw(particle.color);
+                    hline(x, y, 1);
+                }
+                Look::Shard(shard) => {
+                    color::set_draw(particle.color << 4);
+                    let turn = TURNS[(particle.life / 4) as usize % TURNS.len()];
+                    blit(&SHARDS[shard], x - 2, y - 2, 4, 4, BLIT_1BPP | turn);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alive(particles: &Particles) -> usize {
+        particles.pool.iter().filter(|p| p.life > 0).count()
+    }
+
+    #[test]
+    fn busy_screens_recycle_the_pool() {
+        let mut particles = Particles::new(1);
+        particles.burst(Burst::Explosion, (80.0, 80.0));
+        assert_eq!(alive(&particles), 13);
+        for _ in 0..10 {
+            particles.burst(Burst::BossExplosion, (80.0, 80.0));
+        }
+        assert_eq!(alive(&particles), POOL_SIZE);
+    }
+
+    #[test]
+    fn particles_run_out() {
+        let mut particles = Particles::new(1);
+        particles.burst(Burst::Hurt, (80.0, 80.0));
+        for _ in 0..Burst::Hurt.life() {
+            particles.update();
+        }
+        assert_eq!(alive(&particles), 0);
+    }
+}
@@ -0,0 +1,264 @@
+use std::cell::RefCell;
+use super::*;
+
+/// A single call to `tone`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Tone {
+    pub frequency: u32,
+    pub duration: u32,
+    pub volume: u32,
+    pub flags: u32,
+}
+
+/// An in-memory console for running the game on the host.  Drawing happens on
+/// a real framebuffer, input is whatever the gamepad fields are set to, and
+/// text, tones and traces are recorded so they can be inspected afterwards.
+#[derive(Clone)]
+pub struct Headless {
+    pub framebuffer: [u8; FRAMEBUFFER_SIZE],
+    pub palette: [u32; 4],
+    pub draw_colors: u16,
+    pub gamepads: [u8; 4],
+    pub mouse: (i16, i16),
+    pub mouse_buttons: u8,
+    pub disk: Vec<u8>,
+
+    /// Every string drawn with `text` along with its position.
+    pub texts: Vec<(String, i32, i32)>,
+    pub tones: Vec<Tone>,
+    pub traces: Vec<String>,
+}
+
+thread_local! {
+    static HEADLESS: RefCell<Headless> = RefCell::new(Headless::new());
+}
+
+/// Runs `f` with this thread's headless console.
+pub fn with<R>(f: impl FnOnce(&mut Headless) -> R) -> R {
+    HEADLESS.with(|h| f(&mut h.borrow_mut()))
+}
+
+impl Default for Headless {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Headless {
+    /// A console in the same state WASM-4 starts in.
+    pub fn new() -> Self {
+        Self {
+            framebuffer: [0; FRAMEBUFFER_SIZE],
+            palette: [0xe0f8cf, 0x86c06c, 0x306850, 0x071821],
+            draw_colors: 0x1203,
+            gamepads: [0; 4],
+            mouse: (0, 0),
+            mouse_buttons: 0,
+            disk: Vec::new(),
+            texts: Vec::new(),
+            tones: Vec::new(),
+            traces: Vec::new(),
+        }
+    }
+
+    /// Resets everything except the disk, like restarting the cart.
+    pub fn reset(&mut self) {
+        let disk = core::mem::take(&mut self.disk);
+        *self = Self { disk, ..Self::new() };
+    }
+
+    /// Clears the framebuffer and the text drawn during the last frame.  WASM-4
+    /// does this before every update.
+    pub fn next_frame(&mut self) {
+        self.framebuffer = [0; FRAMEBUFFER_SIZE];
+        self.texts.clear();
+    }
+
+    /// The palette index of the pixel at (x, y).
+    pub fn pixel(&self, x: i32, y: i32) -> u8 {
+        let idx = (x + y * SCREEN_SIZE as i32) as usize;
+        let shift = (idx % 4) * 2;
+        (self.framebuffer[idx / 4] >> shift) & 0b11
+    }
+
+    /// Returns if `text` was drawn anywhere this frame.
+    pub fn drew_text(&self, text: &str) -> bool {
+        self.texts.iter().any(|(t, _, _)| t == text)
+    }
+
+    /// Sets the pixel at (x, y) to the palette index `color`, ignoring
+    /// anything off screen.
+    fn set_pixel(&mut self, x: i32, y: i32, color: u8) {
+        let size = SCREEN_SIZE as i32;
+        if x < 0 || y < 0 || x >= size || y >= size {
+            return;
+        }
+        let idx = (x + y * size) as usize;
+        let shift = (idx % 4) * 2;
+        let byte = &mut self.framebuffer[idx / 4];
+        *byte = (*byte & !(0b11 << shift)) | (color << shift);
+    }
+
+    /// The draw color for a pixel of a filled shape.  The outline uses the
+    /// second draw color, unless it is transparent, then it is filled like the
+    /// inside.
+    fn fill_or_outline(&self, edge: bool) -> u16 {
+        if edge && (self.draw_colors >> 4) & 0xf != 0 { 1 } else { 0 }
+    }
+
+    /// Sets the pixel at (x, y) using draw color `n`, where 0 is transparent.
+    fn draw_point(&mut self, n: u16, x: i32, y: i32) {
+        let color = (self.draw_colors >> (n * 4)) & 0xf;
+        if color != 0 {
+            self.set_pixel(x, y, (color - 1) as u8 & 0b11);
+        }
+    }
+}
+
+impl Platform for Headless {
+    fn blit(&mut self, sprite: &[u8], x: i32, y: i32, width: u32, height: u32, flags: u32) {
+        let bpp2 = flags & BLIT_2BPP != 0;
+        let rotate = flags & BLIT_ROTATE != 0;
+        let flip_x = (flags & BLIT_FLIP_X != 0) != rotate;
+        let flip_y = flags & BLIT_FLIP_Y != 0;
+
+        for sy in 0..height as i32 {
+            for sx in 0..width as i32 {
+                let (tx, ty) = if rotate {
+                    (x + sy, y + sx)
+                } else {
+                    (x + sx, y + sy)
+                };
+                let px = if flip_x { width as i32 - sx - 1 } else { sx };
+                let py = if flip_y { height as i32 - sy - 1 } else { sy };
+
+                let bit = (py * width as i32 + px) as usize;
+                let color_idx = if bpp2 {
+                    (sprite[bit / 4] >> (6 - (bit % 4) * 2)) & 0b11
+                } else {
+                    (sprite[bit / 8] >> (7 - bit % 8)) & 0b1
+                };
+                self.draw_point(color_idx as u16, tx, ty);
+            }
+        }
+    }
+
+    fn line(&mut self, x1: i32, y1: i32, x2: i32, y2: i32) {
+        let (dx, dy) = ((x2 - x1).abs(), -(y2 - y1).abs());
+        let (step_x, step_y) = (if x1 < x2 { 1 } else { -1 }, if y1 < y2 { 1 } else { -1 });
+        let (mut x, mut y) = (x1, y1);
+        let mut err = dx + dy;
+        loop {
+            self.draw_point(0, x, y);
+            if x == x2 && y == y2 {
+                break;
+            }
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x += step_x;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += step_y;
+            }
+        }
+    }
+
+    fn hline(&mut self, x: i32, y: i32, len: u32) {
+        for i in 0..len as i32 {
+            self.draw_point(0, x + i, y);
+        }
+    }
+
+    fn vline(&mut self, x: i32, y: i32, len: u32) {
+        for i in 0..len as i32 {
+            self.draw_point(0, x, y + i);
+        }
+    }
+
+    fn rect(&mut self, x: i32, y: i32, width: u32, height: u32) {
+        let (w, h) = (width as i32, height as i32);
+        for j in 0..h {
+            for i in 0..w {
+                let edge = i == 0 || j == 0 || i == w - 1 || j == h - 1;
+                self.draw_point(self.fill_or_outline(edge), x + i, y + j);
+            }
+        }
+    }
+
+    fn oval(&mut self, x: i32, y: i32, width: u32, height: u32) {
+        let (a, b) = (width as f32 / 2.0, height as f32 / 2.0);
+        let inside = |i: i32, j: i32| {
+            let dx = (i as f32 + 0.5 - a) / a;
+            let dy = (j as f32 + 0.5 - b) / b;
+            dx * dx + dy * dy <= 1.0
+        };
+        let (w, h) = (width as i32, height as i32);
+        for j in 0..h {
+            for i in 0..w {
+                if inside(i, j) {
+                    let edge = !inside(i - 1, j) || !inside(i + 1, j)
+                        || !inside(i, j - 1) || !inside(i, j + 1);
+                    self.draw_point(self.fill_or_outline(edge), x + i, y + j);
+                }
+            }
+        }
+    }
+
+    fn text(&mut self, text: &str, x: i32, y: i32) {
+        self.texts.push((text.to_string(), x, y));
+    }
+
+    fn framebuffer(&mut self) -> &mut [u8; FRAMEBUFFER_SIZE] {
+        &mut self.framebuffer
+    }
+
+    fn palette(&self) -> [u32; 4] {
+        self.palette
+    }
+
+    fn set_palette(&mut self, palette: [u32; 4]) {
+        self.palette = palette;
+    }
+
+    fn draw_colors(&self) -> u16 {
+        self.draw_colors
+    }
+
+    fn set_draw_colors(&mut self, colors: u16) {
+        self.draw_colors = colors;
+    }
+
+    fn tone(&mut self, frequency: u32, duration: u32, volume: u32, flags: u32) {
+        self.tones.push(Tone { frequency, duration, volume, flags });
+    }
+
+    fn gamepad(&self, idx: usize) -> u8 {
+        self.gamepads[idx.min(3)]
+    }
+
+    fn mouse(&self) -> (i16, i16) {
+        self.mouse
+    }
+
+    fn mouse_buttons(&self) -> u8 {
+        self.mouse_buttons
+    }
+
+    fn disk_read(&mut self, dest: &mut [u8]) -> u32 {
+        let n = dest.len().min(self.disk.len());
+        dest[..n].copy_from_slice(&self.disk[..n]);
+        n as u32
+    }
+
+    fn disk_write(&mut self, src: &[u8]) -> u32 {
+        let n = src.len().min(DISK_SIZE);
+        self.disk = src[..n].to_vec();
+        n as u32
+    }
+
+    fn trace(&mut self, text: &str) {
+        self.traces.push(text.to_string());
+    }
+}
@@ -0,0 +1,192 @@
+//! Everything the game needs from the console: graphics, audio, input,
+//! palette and disk.  The game only talks to the `Platform` trait through the
+//! free functions in this module, so the same code runs on the WASM-4 runtime
+//! and headless on the host (for `cargo test`).
+
+pub use crate::wasm4::sys::{
+    SCREEN_SIZE,
+    BLIT_1BPP, BLIT_2BPP, BLIT_FLIP_X, BLIT_FLIP_Y, BLIT_ROTATE,
+    BUTTON_1, BUTTON_2, BUTTON_LEFT, BUTTON_RIGHT, BUTTON_UP, BUTTON_DOWN,
+    MOUSE_LEFT, MOUSE_RIGHT, MOUSE_MIDDLE,
+    TONE_PULSE1, TONE_PULSE2, TONE_TRIANGLE, TONE_NOISE,
+    TONE_MODE1, TONE_MODE2, TONE_MODE3, TONE_MODE4,
+};
+
+#[cfg(target_arch = "wasm32")]
+mod wasm;
+#[cfg(target_arch = "wasm32")]
+pub use wasm::Wasm4 as Backend;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub mod headless;
+#[cfg(not(target_arch = "wasm32"))]
+pub use headless::Headless as Backend;
+
+/// Size of the framebuffer in bytes, 2 bits per pixel.
+pub const FRAMEBUFFER_SIZE: usize = (SCREEN_SIZE * SCREEN_SIZE / 4) as usize;
+
+/// Size of the persistent disk in bytes.
+pub const DISK_SIZE: usize = 1024;
+
+/// A backend the game can run on.
+pub trait Platform {
+    /// Copies pixels to the framebuffer.
+    fn blit(&mut self, sprite: &[u8], x: i32, y: i32, width: u32, height: u32, flags: u32);
+
+    /// Draws a line between two points.
+    fn line(&mut self, x1: i32, y1: i32, x2: i32, y2: i32);
+
+    /// Draws a horizontal line.
+    fn hline(&mut self, x: i32, y: i32, len: u32);
+
+    /// Draws a vertical line.
+    fn vline(&mut self, x: i32, y: i32, len: u32);
+
+    /// Draws a rectangle.
+    fn rect(&mut self, x: i32, y: i32, width: u32, height: u32);
+
+    /// Draws an oval (or circle).
+    fn oval(&mut self, x: i32, y: i32, width: u32, height: u32);
+
+    /// Draws text using the built-in system font.
+    fn text(&mut self, text: &str, x: i32, y: i32);
+
+    /// Mutable reference to the raw framebuffer.
+    fn framebuffer(&mut self) -> &mut [u8; FRAMEBUFFER_SIZE];
+
+    /// The four colors of the current palette.
+    fn palette(&self) -> [u32; 4];
+
+    /// Replaces the current palette.
+    fn set_palette(&mut self, palette: [u32; 4]);
+
+    /// The draw color indices used by the drawing functions.
+    fn draw_colors(&self) -> u16;
+
+    /// Sets the draw color indices used by the drawing functions.
+    fn set_draw_colors(&mut self, colors: u16);
+
+    /// Plays a sound tone.
+    fn tone(&mut self, frequency: u32, duration: u32, volume: u32, flags: u32);
+
+    /// The button state of gamepad `idx`, from 0 to 3.
+    fn gamepad(&self, idx: usize) -> u8;
+
+    /// The current mouse position.
+    fn mouse(&self) -> (i16, i16);
+
+    /// The current mouse button state.
+    fn mouse_buttons(&self) -> u8;
+
+    /// Reads up to `dest.len()` bytes from persistent storage, returning the
+    /// number of bytes read.
+    fn disk_read(&mut self, dest: &mut [u8]) -> u32;
+
+    /// Writes `src` to persistent storage, returning the number of bytes written.
+    fn disk_write(&mut self, src: &[u8]) -> u32;
+
+    /// Prints a message to the debug console.
+    fn trace(&mut self, text: &str);
+}
+
+/// Runs `f` with the backend the game is running on.
+#[cfg(target_arch = "wasm32")]
+pub fn with<R>(f: impl FnOnce(&mut Backend) -> R) -> R {
+    f(&mut wasm::Wasm4)
+}
+
+/// Runs `f` with the backend the game is running on.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn with<R>(f: impl FnOnce(&mut Backend) -> R) -> R {
+    headless::with(f)
+}
+
+/// Copies pixels to the framebuffer.
+pub fn blit(sprite: &[u8], x: i32, y: i32, width: u32, height: u32, flags: u32) {
+    with(|p| p.blit(sprite, x, y, width, height, flags))
+}
+
+/// Draws a line between two points.
+pub fn line(x1: i32, y1: i32, x2: i32, y2: i32) {
+    with(|p| p.line(x1, y1, x2, y2))
+}
+
+/// Draws a horizontal line.
+pub fn hline(x: i32, y: i32, len: u32) {
+    with(|p| p.hline(x, y, len))
+}
+
+/// Draws a vertical line.
+pub fn vline(x: i32, y: i32, len: u32) {
+    with(|p| p.vline(x, y, len))
+}
+
+/// Draws a rectangle.
+pub fn rect(x: i32, y: i32, width: u32, height: u32) {
+    with(|p| p.rect(x, y, width, height))
+}
+
+/// Draws an oval (or circle).
+pub fn oval(x: i32, y: i32, width: u32, height: u32) {
+    with(|p| p.oval(x, y, width, height))
+}
+
+/// Draws text using the built-in system font.
+pub fn text<T: AsRef<str>>(text: T, x: i32, y: i32) {
+    with(|p| p.text(text.as_ref(), x, y))
+}
+
+/// Runs `f` with a mutable reference to the raw framebuffer.
+pub fn framebuffer<R>(f: impl FnOnce(&mut [u8; FRAMEBUFFER_SIZE]) -> R) -> R {
+    with(|p| f(p.framebuffer()))
+}
+
+/// The four colors of the current palette.
+pub fn palette() -> [u32; 4] {
+    with(|p| p.palette())
+}
+
+/// Replaces the current palette.
+pub fn set_palette(palette: [u32; 4]) {
+    with(|p| p.set_palette(palette))
+}
+
+/// Sets the draw color indices used by the drawing functions.
+pub fn set_draw_colors(colors: u16) {
+    with(|p| p.set_draw_colors(colors))
+}
+
+/// Plays a sound tone.
+pub fn tone(frequency: u32, duration: u32, volume: u32, flags: u32) {
+    with(|p| p.tone(frequency, duration, volume, flags))
+}
+
+/// The button state of gamepad `idx`, from 0 to 3.
+pub fn gamepad(idx: usize) -> u8 {
+    with(|p| p.gamepad(idx))
+}
+
+/// The current mouse position.
+pub fn mouse() -> (i16, i16) {
+    with(|p| p.mouse())
+}
+
+/// The current mouse button state.
+pub fn mouse_buttons() -> u8 {
+    with(|p| p.mouse_buttons())
+}
+
+/// Reads up to `dest.len()` bytes from persistent storage.
+pub fn disk_read(dest: &mut [u8]) -> u32 {
+    with(|p| p.disk_read(dest))
+}
+
+/// Writes `src` to persistent storage.
+pub fn disk_write(src: &[u8]) -> u32 {
+    with(|p| p.disk_write(src))
+}
+
+/// Prints a message to the debug console.
+pub fn trace<T: AsRef<str>>(text: T) {
+    with(|p| p.trace(text.as_ref()))
+}
@@ -0,0 +1,90 @@
+use crate::wasm4::sys;
+use super::*;
+
+/// The WASM-4 runtime itself.  Every call goes straight to the imported
+/// functions and memory addresses in `wasm4::sys`.
+pub struct Wasm4;
+
+impl Platform for Wasm4 {
+    fn blit(&mut self, sprite: &[u8], x: i32, y: i32, width: u32, height: u32, flags: u32) {
+        sys::blit(sprite, x, y, width, height, flags);
+    }
+
+    fn line(&mut self, x1: i32, y1: i32, x2: i32, y2: i32) {
+        sys::line(x1, y1, x2, y2);
+    }
+
+    fn hline(&mut self, x: i32, y: i32, len: u32) {
+        sys::hline(x, y, len);
+    }
+
+    fn vline(&mut self, x: i32, y: i32, len: u32) {
+        sys::vline(x, y, len);
+    }
+
+    fn rect(&mut self, x: i32, y: i32, width: u32, height: u32) {
+        sys::rect(x, y, width, height);
+    }
+
+    fn oval(&mut self, x: i32, y: i32, width: u32, height: u32) {
+        sys::oval(x, y, width, height);
+    }
+
+    fn text(&mut self, text: &str, x: i32, y: i32) {
+        sys::text(text, x, y);
+    }
+
+    fn framebuffer(&mut self) -> &mut [u8; FRAMEBUFFER_SIZE] {
+        unsafe { &mut *sys::FRAMEBUFFER }
+    }
+
+    fn palette(&self) -> [u32; 4] {
+        unsafe { *sys::PALETTE }
+    }
+
+    fn set_palette(&mut self, palette: [u32; 4]) {
+        unsafe { *sys::PALETTE = palette }
+    }
+
+    fn draw_colors(&self) -> u16 {
+        unsafe { *sys::DRAW_COLORS }
+    }
+
+    fn set_draw_colors(&mut self, colors: u16) {
+        unsafe { *sys::DRAW_COLORS = colors }
+    }
+
+    fn tone(&mut self, frequency: u32, duration: u32, volume: u32, flags: u32) {
+        sys::tone(frequency, duration, volume, flags);
+    }
+
+    fn gamepad(&self, idx: usize) -> u8 {
+        let gamepad = match idx {
+            0 => sys::GAMEPAD1,
+            1 => sys::GAMEPAD2,
+            2 => sys::GAMEPAD3,
+            _ => sys::GAMEPAD4,
+        };
+        unsafe { *gamepad }
+    }
+
+    fn mouse(&self) -> (i16, i16) {
+        unsafe { (*sys::MOUSE_X, *sys::MOUSE_Y) }
+    }
+
+    fn mouse_buttons(&self) -> u8 {
+        unsafe { *sys::MOUSE_BUTTONS }
+    }
+
+    fn disk_read(&mut self, dest: &mut [u8]) -> u32 {
+        unsafe { sys::diskr(dest.as_mut_ptr(), dest.len() as u32) }
+    }
+
+    fn disk_write(&mut self, src: &[u8]) -> u32 {
+        unsafe { sys::diskw(src.as_ptr(), src.len() as u32) }
+    }
+
+    fn trace(&mut self, text: &str) {
+        sys::trace(text);
+    }
+}
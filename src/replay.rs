@@ -0,0 +1,286 @@
+//! Recording and replaying runs.  A run is fully determined by the seed of
+//! its `Random` and the gamepad byte read every time the game advances its
+//! controls, so that is all a `Recording` stores.
+//!
+//! The input is run-length encoded with a byte for each change: the top 3
+//! bits are the gamepad bit that flipped and the bottom 5 how many frames
+//! the gamepad then stays the same for.  A tap of fire is two changes, and
+//! players tap fire far more often than they do anything else, so this is
+//! half the size of storing the whole gamepad byte with each run.
+
+use crate::platform;
+
+/// Most bytes of input a recording will hold, 2KB of the 16KB heap.
+/// Anything after is dropped and the recording is marked as truncated, since
+/// it can no longer be replayed.  Tapping fire every few frames while
+/// weaving about takes around 16 bytes a second, so this is two minutes of
+/// frantic play, or eight of tapping a few times a second.
+const MAX_BYTES: usize = 2048;
+
+/// Most frames a single byte holds the gamepad for.
+const MAX_FRAMES: u8 = 0b11111;
+
+/// Gamepad bit 2 is never set, so a change to it instead means nothing
+/// changed.  It holds the gamepad for longer than `MAX_FRAMES`.
+const WAIT: u8 = 2;
+
+/// Digits for writing bytes as hex.
+const HEX: &[u8; 16] = b"0123456789abcdef";
+
+/// The gamepad after applying a single change byte to `gamepad`.
+fn apply(gamepad: u8, change: u8) -> u8 {
+    match change >> 5 {
+        WAIT => gamepad,
+        bit => gamepad ^ 1 << bit,
+    }
+}
+
+/// The seed and run-length encoded input of a single run.
+#[derive(Clone, Default, Debug)]
+pub struct Recording {
+    pub seed: u32,
+    /// A byte for each change to the gamepad, see the module docs.
+    changes: Vec<u8>,
+    /// The gamepad after the last change.
+    gamepad: u8,
+    /// Input was dropped after `MAX_BYTES`, so replaying it would go wrong.
+    pub truncated: bool,
+}
+
+impl Recording {
+    pub fn new(seed: u32) -> Self {
+        Self {
+            seed,
+            ..Default::default()
+        }
+    }
+
+    /// Appends a single frame of input, unless input has already been
+    /// dropped.
+    pub fn push(&mut self, gamepad: u8) {
+        let gamepad = gamepad & !(1 << WAIT);
+        let changed = gamepad ^ self.gamepad;
+        for bit in (0..8).filter(|bit| changed & 1 << bit != 0) {
+            self.add(bit << 5);
+        }
+        if self.truncated {
+            return;
+        }
+        self.gamepad = gamepad;
+        match self.changes.last_mut() {
+            Some(last) if *last & MAX_FRAMES < MAX_FRAMES => *last += 1,
+            _ => self.add(WAIT << 5 | 1),
+        }
+    }
+
+    /// Adds a change byte, or drops it and marks the recording truncated
+    /// once it is full.
+    fn add(&mut self, change: u8) {
+        if self.truncated || self.changes.len() >= MAX_BYTES {
+            self.truncated = true;
+        } else {
+            self.changes.push(change);
+        }
+    }
+
+    /// Total number of frames recorded.
+    pub fn frames(&self) -> u32 {
+        self.changes.iter().map(|c| (c & MAX_FRAMES) as u32).sum()
+    }
+
+    /// The seed as 4 little endian bytes, followed by the change bytes.
+    pub fn bytes(&self) -> impl Iterator<Item = u8> + '_ {
+        self.seed.to_le_bytes().into_iter().chain(self.changes.iter().copied())
+    }
+
+    /// Reads a recording written by `bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < 4 {
+            return None;
+        }
+        let seed = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        let changes = bytes[4..].to_vec();
+        let gamepad = changes.iter().fold(0, |gamepad, &c| apply(gamepad, c));
+        Some(Self { seed, changes, gamepad, truncated: false })
+    }
+
+    /// Writes the recording to the console as lines of hex, which
+    /// `from_hex` reads back, so a run that went wrong can be replayed from
+    /// a bug report.
+    pub fn trace(&self) {
+        platform::trace("Recording:");
+        let mut line = String::new();
+        for (i, byte) in self.bytes().enumerate() {
+            line.push(HEX[(byte >> 4) as usize] as char);
+            line.push(HEX[(byte & 0xf) as usize] as char);
+            if i % 32 == 31 {
+                platform::trace(&line);
+                line.clear();
+            }
+        }
+        if !line.is_empty() {
+            platform::trace(&line);
+        }
+    }
+
+    /// Reads a recording from the lines of hex written by `trace`.
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let digits: Vec<u8> = hex
+            .chars()
+            .filter(|c| !c.is_ascii_whitespace())
+            .map(|c| c.to_digit(16).map(|d| d as u8))
+            .collect::<Option<_>>()?;
+        if !digits.len().is_multiple_of(2) {
+            return None;
+        }
+        let bytes: Vec<u8> = digits.chunks(2).map(|d| d[0] << 4 | d[1]).collect();
+        Self::from_bytes(&bytes)
+    }
+}
+
+/// Steps through a recording one frame at a time.
+#[derive(Clone, Debug)]
+pub struct Playback {
+    recording: Recording,
+    /// Index of the next change byte.
+    change: usize,
+    gamepad: u8,
+    /// Frames left before the next change.
+    frames: u8,
+}
+
+impl Playback {
+    pub fn new(recording: Recording) -> Self {
+        Self {
+            recording,
+            change: 0,
+            gamepad: 0,
+            frames: 0,
+        }
+    }
+
+    pub fn into_recording(self) -> Recording {
+        self.recording
+    }
+}
+
+impl Iterator for Playback {
+    type Item = u8;
+
+    /// The gamepad byte for the next frame, or `None` once the recording is over.
+    fn next(&mut self) -> Option<u8> {
+        while self.frames == 0 {
+            let change = *self.recording.changes.get(self.change)?;
+            self.change += 1;
+            self.gamepad = apply(self.gamepad, change);
+            self.frames = change & MAX_FRAMES;
+        }
+        self.frames -= 1;
+        Some(self.gamepad)
+    }
+}
+
+/// Where `Controls` gets the gamepad byte from each frame.
+#[derive(Clone, Debug, Default)]
+pub enum Source {
+    /// Read the gamepad.
+    #[default]
+    Gamepad,
+    /// Read the gamepad and record what was read.
+    Record(Recording),
+    /// Feed back a recording, ignoring the gamepad.
+    Replay(Playback),
+}
+
+impl Source {
+    /// The byte for gamepad `idx` this frame, with only the buttons in
+    /// `mask` read from a live gamepad.  Recordings already hold the masked
+    /// byte.
+    pub fn read(&mut self, idx: usize, mask: u8) -> u8 {
+        match self {
+            Source::Gamepad => platform::gamepad(idx) & mask,
+            Source::Record(recording) => {
+                let gamepad = platform::gamepad(idx) & mask;
+                recording.push(gamepad);
+                gamepad
+            }
+            Source::Replay(playback) => playback.next().unwrap_or(0),
+        }
+    }
+
+    /// Stops recording or replaying, handing back the recording.
+    pub fn finish(&mut self) -> Option<Recording> {
+        match core::mem::take(self) {
+            Source::Gamepad => None,
+            Source::Record(recording) => Some(recording),
+            Source::Replay(playback) => Some(playback.into_recording()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Input with short runs, several buttons changing at once, a run too
+    /// long for one byte and a change on the very last frame.
+    fn input() -> Vec<u8> {
+        let mut input = vec![0, 0, 16, 16, 16, 1, 17, 0, 48, 48];
+        input.extend([32; 600]);
+        input.extend([0, 33]);
+        input
+    }
+
+    fn record(input: &[u8]) -> Recording {
+        let mut recording = Recording::new(7);
+        for &gamepad in input {
+            recording.push(gamepad);
+        }
+        recording
+    }
+
+    #[test]
+    fn round_trip() {
+        let recording = record(&input());
+
+        assert!(!recording.truncated);
+        assert_eq!(recording.frames(), input().len() as u32);
+        let played: Vec<u8> = Playback::new(recording).collect();
+        assert_eq!(played, input());
+    }
+
+    #[test]
+    fn round_trip_through_bytes() {
+        let bytes: Vec<u8> = record(&input()).bytes().collect();
+        let mut recording = Recording::from_bytes(&bytes).unwrap();
+        assert_eq!(recording.seed, 7);
+
+        // carries on recording from where it left off
+        recording.push(1);
+        let mut expected = input();
+        expected.push(1);
+        assert_eq!(Playback::new(recording).collect::<Vec<u8>>(), expected);
+
+        assert!(Recording::from_bytes(&bytes[..3]).is_none());
+    }
+
+    #[test]
+    fn taps_take_a_byte_a_change() {
+        let taps: Vec<u8> = (0..100).map(|frame| (frame / 3 % 2) as u8).collect();
+        let recording = record(&taps);
+        assert_eq!(recording.changes.len(), 100 / 3 + 1);
+    }
+
+    #[test]
+    fn truncates_when_full() {
+        let input: Vec<u8> = (0..MAX_BYTES + 10).map(|i| i as u8 % 2).collect();
+        let mut recording = record(&input);
+
+        assert!(recording.truncated);
+        assert_eq!(recording.changes.len(), MAX_BYTES);
+        // stays truncated even if the gamepad settles down
+        recording.push(1);
+        recording.push(1);
+        assert_eq!(Playback::new(recording).count(), MAX_BYTES);
+    }
+}
@@ -0,0 +1,139 @@
+//! Everything kept on the 1KB WASM-4 disk.  The disk starts with a magic
+//! number and a version byte, and anything that doesn't match is treated as
+//! an empty save.  The sections follow one after another, and a section
+//! missing from the end of an older save is read as its default.
+//!
+//! Version 1 saves had a single high score table, which is kept as the table
+//! for normal difficulty.  Version 2 saves had no achievements.
+
+use crate::platform;
+use crate::{HighScores, Preset};
+use crate::options::Options;
+
+const MAGIC: [u8; 2] = *b"FP";
+const VERSION: u8 = 3;
+
+#[derive(Clone, Default, Debug)]
+pub struct Save {
+    /// A table for each difficulty preset.
+    pub high_scores: [HighScores; Preset::ALL.len()],
+    /// A bit for each unlocked `Achievement`.
+    pub achievements: u32,
+    pub options: Options,
+}
+
+impl Save {
+    /// Reads the save from disk, or the default if there isn't one.
+    pub fn load() -> Self {
+        let mut bytes = [0; platform::DISK_SIZE];
+        let len = platform::disk_read(&mut bytes) as usize;
+        Self::from_bytes(&bytes[..len]).unwrap_or_default()
+    }
+
+    /// Writes the save to disk.
+    pub fn store(&self) {
+        platform::disk_write(&self.to_bytes());
+    }
+
+    pub fn high_scores(&self, preset: Preset) -> &HighScores {
+        &self.high_scores[preset as usize]
+    }
+
+    pub fn high_scores_mut(&mut self, preset: Preset) -> &mut HighScores {
+        &mut self.high_scores[preset as usize]
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(VERSION);
+        for table in &self.high_scores {
+            bytes.extend(table.to_bytes());
+        }
+        bytes.extend(self.achievements.to_le_bytes());
+        bytes.extend(self.options.to_bytes());
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < 3 || bytes[..2] != MAGIC {
+            return None;
+        }
+        let mut save = Self::default();
+        let mut rest = &bytes[3..];
+        match bytes[2] {
+            1 => {
+                let table = HighScores::from_bytes(rest)?;
+                rest = &rest[table.byte_len()..];
+                *save.high_scores_mut(Preset::Normal) = table;
+            }
+            2 | VERSION => {
+                for table in save.high_scores.iter_mut() {
+                    *table = HighScores::from_bytes(rest)?;
+                    rest = &rest[table.byte_len()..];
+                }
+                if bytes[2] == VERSION {
+                    save.achievements = u32::from_le_bytes(*rest.first_chunk()?);
+                    rest = &rest[4..];
+                }
+            }
+            _ => return None,
+        }
+        save.options = Options::from_bytes(rest);
+        Some(save)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Theme;
+    use crate::{Cycle, HighScore, TABLE_SIZE};
+
+    /// A save with every table full, which is as big as a save gets.
+    fn full() -> Save {
+        let mut save = Save { achievements: 0b1010_0101, ..Default::default() };
+        save.options.sound = false;
+        save.options.theme = Theme::Colorsafe;
+        save.options.preset = Preset::Hard;
+        for (i, table) in save.high_scores.iter_mut().enumerate() {
+            for score in 0..TABLE_SIZE as u32 {
+                table.insert(HighScore::new(100 * i as u32 + score + 1, score, 2, Cycle::Night));
+            }
+        }
+        save
+    }
+
+    #[test]
+    fn round_trip() {
+        let save = full();
+        let bytes = save.to_bytes();
+        assert!(bytes.len() <= platform::DISK_SIZE);
+
+        let loaded = Save::from_bytes(&bytes).unwrap();
+        assert_eq!(loaded.high_scores, save.high_scores);
+        assert_eq!(loaded.achievements, save.achievements);
+        assert_eq!(loaded.options, save.options);
+    }
+
+    #[test]
+    fn version_1_scores_go_in_the_normal_table() {
+        let table = full().high_scores[0].clone();
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(1);
+        bytes.extend(table.to_bytes());
+
+        let loaded = Save::from_bytes(&bytes).unwrap();
+        assert_eq!(loaded.high_scores(Preset::Normal), &table);
+        assert!(loaded.high_scores(Preset::Easy).scores().is_empty());
+        assert_eq!(loaded.achievements, 0);
+    }
+
+    #[test]
+    fn rejects_anything_else() {
+        assert!(Save::from_bytes(&[]).is_none());
+        assert!(Save::from_bytes(b"XX\x01").is_none());
+        assert!(Save::from_bytes(&[MAGIC[0], MAGIC[1], VERSION + 1]).is_none());
+        // a table that runs off the end
+        assert!(Save::from_bytes(&[MAGIC[0], MAGIC[1], VERSION, 3, 0]).is_none());
+    }
+}
@@ -1,21 +1,35 @@
-use crate::wasm4::sys::*;
+use core::sync::atomic::{AtomicBool, Ordering};
+use crate::platform::*;
+
+static ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Turns every sound effect on or off.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+fn play(frequency: u32, duration: u32, volume: u32, flags: u32) {
+    if ENABLED.load(Ordering::Relaxed) {
+        tone(frequency, duration, volume, flags);
+    }
+}
 
 pub fn enemy_fire() {
-    tone(230 | (100 << 16), 10, 20, TONE_PULSE1);
+    play(230 | (100 << 16), 10, 20, TONE_PULSE1);
 }
 
 pub fn player_fire() {
-    tone(440 | (320 << 16), 6, 51, TONE_PULSE1);
+    play(440 | (320 << 16), 6, 51, TONE_PULSE1);
 }
 
 pub fn enemy_death() {
-    tone(260 | (120 << 16), 56 << 8, 30, TONE_NOISE);
+    play(260 | (120 << 16), 56 << 8, 30, TONE_NOISE);
 }
 
 pub fn player_death() {
-    tone(370 | (250 << 16), 22 | 90 << 8, 30, TONE_NOISE);
+    play(370 | (250 << 16), 22 | 90 << 8, 30, TONE_NOISE);
 }
 
 pub fn player_damage() {
-    tone(350 | (380 << 16), 32 << 8, 30, TONE_NOISE);
+    play(350 | (380 << 16), 32 << 8, 30, TONE_NOISE);
 }
@@ -1,30 +1,22 @@
 mod sprite_data;
 pub use sprite_data::Sprite;
 
-use crate::wasm4::sys::*;
+use crate::platform::*;
 
 use crate::util;
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Copy, Default)]
 pub struct SpriteData {
     pub width: u32,
     pub height: u32,
     pub flags: u32,
-    pub data: Vec<u8>,
+    pub data: &'static [u8],
+    /// Which pixels aren't transparent, one row of bits per row of pixels
+    /// with the leftmost pixel in the lowest bit.
+    pub mask: &'static [u32],
 }
 
 impl SpriteData {
-    pub fn range_iterator(&self, left: i32, right: i32, top: i32, bottom: i32) -> SpriteIterator {
-        SpriteIterator {
-            left: left as u32, 
-            top: top as u32,
-            width: (right - left) as u32,
-            height: (bottom - top) as u32,
-            idx: 0,
-            sprite: self.clone(),
-        }
-    }
-
     pub fn pixel(&self, x: usize, y: usize) -> u8 {
         let pixel_idx = y * self.width as usize + x;
         let byte_idx = pixel_idx / 4;
@@ -33,15 +25,27 @@ impl SpriteData {
         let sprite_byte = self.data[byte_idx];
         util::bit_range(
             sprite_byte, 
-            byte_offset as usize * 2, 
-            (byte_offset as usize + 1) * 2,
+            byte_offset * 2, 
+            (byte_offset + 1) * 2,
         )
     }
 
+    /// Checks if any pixel in a `width` by `height` area is opaque in both
+    /// sprites, where the area starts at `at` in this sprite and at
+    /// `other_at` in the other.
+    pub fn overlaps(&self, at: (i32, i32), other: &SpriteData, other_at: (i32, i32), width: u32, height: u32) -> bool {
+        let area = u32::MAX >> (u32::BITS - width);
+        (0..height as i32).any(|y| {
+            let row = self.mask[(at.1 + y) as usize] >> at.0;
+            let other_row = other.mask[(other_at.1 + y) as usize] >> other_at.0;
+            row & other_row & area != 0
+        })
+    }
+
     /// Draws the current sprite with the top left pixel at (x, y)
     pub fn draw(&self, x: i32, y: i32) {
         blit(
-            &self.data, 
+            self.data, 
             x,
             y,
             self.width, 
@@ -51,46 +55,33 @@ impl SpriteData {
     }
 }
 
-impl IntoIterator for SpriteData {
-    type IntoIter = SpriteIterator;
-    type Item = u8;
-    
-    fn into_iter(self) -> Self::IntoIter {
-        Self::IntoIter {
-            left: 0,
-            width: self.width,
-            top: 0,
-            height: self.height,
-            idx: 0,
-            sprite: self,
+/// Works out the opacity mask of a `width` pixel wide sprite from its 2bpp
+/// data while compiling, so the masks sit in the binary next to the sprites.
+const fn mask<const HEIGHT: usize>(data: &[u8], width: u32) -> [u32; HEIGHT] {
+    assert!(width <= u32::BITS, "sprites must fit in a mask row");
+    let width = width as usize;
+    let mut rows = [0; HEIGHT];
+    let mut i = 0;
+    while i < width * HEIGHT {
+        if data[i / 4] >> (6 - 2 * (i % 4)) & 0b11 != 0 {
+            rows[i / width] |= 1 << (i % width);
         }
+        i += 1;
     }
+    rows
 }
 
-pub struct SpriteIterator {
-    sprite: SpriteData,
-    left: u32,
-    width: u32,
-    top: u32,
-    height: u32,
-    idx: u32,
-}
-
-impl Iterator for SpriteIterator {
-    type Item = u8;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.idx >= self.width * self.height {
-            return None
+    #[test]
+    fn every_sprite_is_static_and_whole() {
+        for &sprite in Sprite::ALL {
+            let data = sprite.get();
+            assert!(core::ptr::eq(data, sprite.get()), "{sprite:?}");
+            assert_eq!(data.data.len() as u32, (data.width * data.height).div_ceil(4), "{sprite:?}");
+            assert_eq!(data.mask.len() as u32, data.height, "{sprite:?}");
         }
-
-        let x = self.idx % self.width + self.left;
-        let y = self.idx / self.width + self.top;
-        
-        let pixel = self.sprite.pixel(x as usize, y as usize);
-
-        self.idx += 1;
-        // return value
-        Some(pixel)
     }
 }
@@ -33,12 +33,17 @@
 
 
 use crate::SpriteData;
+use super::mask;
 
 #[derive(Clone, Copy, Debug)]
 pub enum Sprite {
     #[allow(non_camel_case_types)]
     arrow,
     #[allow(non_camel_case_types)]
+    boss1,
+    #[allow(non_camel_case_types)]
+    boss2,
+    #[allow(non_camel_case_types)]
     bullet1,
     #[allow(non_camel_case_types)]
     bullet2,
@@ -108,222 +113,428 @@ pub enum Sprite {
     turret9,
 }
 
-impl Sprite {
-    pub fn get(self) -> SpriteData {
-        use Sprite::*;
-        match self {
-            arrow => SpriteData {
-                width: 8,
-                height: 8,
-                flags: 1,
-                data: vec![ 0x3c,0x00,0x3f,0x00,0x3f,0xc0,0x3f,0xf0,0x3f,0xf0,0x3f,0xc0,0x3f,0x00,0x3c,0x00 ],
-            },
-            bullet1 => SpriteData {
-                width: 4,
-                height: 4,
-                flags: 1,
-                data: vec![ 0x28,0xac,0xe8,0x30 ],
-            },
-            bullet2 => SpriteData {
-                width: 4,
-                height: 4,
-                flags: 1,
-                data: vec![ 0x20,0xb8,0xe8,0x38 ],
-            },
-            bullet3 => SpriteData {
-                width: 4,
-                height: 4,
-                flags: 1,
-                data: vec![ 0x20,0xae,0x3b,0x2c ],
-            },
-            bullet4 => SpriteData {
-                width: 4,
-                height: 4,
-                flags: 1,
-                data: vec![ 0x28,0xee,0xb8,0x20 ],
-            },
-            enemy1 => SpriteData {
-                width: 16,
-                height: 16,
-                flags: 1,
-                data: vec![ 0x00,0x40,0x08,0x00,0x01,0x40,0x0a,0x00,0x05,0x40,0x0b,0x80,0x05,0x80,0x0a,0xc0,0x16,0x60,0x3b,0xf0,0x15,0xaa,0xae,0xf0,0x16,0xaa,0xab,0xf0,0x15,0xaa,0xaa,0xf0,0x16,0x6a,0xab,0xf0,0x15,0xa8,0xbb,0xf0,0x05,0x68,0xaf,0xc0,0x01,0x68,0xaf,0x00,0x00,0x50,0x2c,0x00,0x00,0x10,0x20,0x00,0x00,0x10,0x20,0x00,0x00,0x10,0x20,0x00 ],
-            },
-            enemy2 => SpriteData {
-                width: 16,
-                height: 16,
-                flags: 1,
-                data: vec![ 0x00,0x00,0x02,0x00,0x00,0x40,0x02,0x00,0x00,0x40,0x02,0x20,0x01,0x40,0x02,0xb0,0x05,0xa0,0x00,0x3c,0x15,0x00,0x00,0x00,0x10,0x02,0xac,0x00,0x01,0x0a,0xab,0xc0,0x16,0x6a,0xaa,0xc0,0x05,0xa8,0x80,0x00,0x01,0x68,0x0b,0xc0,0x01,0x68,0x2b,0xc0,0x00,0x50,0x0b,0x00,0x00,0x10,0x08,0x00,0x00,0x10,0x00,0x00,0x00,0x00,0x00,0x00 ],
-            },
-            enemy3 => SpriteData {
-                width: 16,
-                height: 16,
-                flags: 1,
-                data: vec![ 0x01,0x00,0x00,0x00,0x01,0x00,0x00,0x80,0x01,0x00,0x00,0x08,0x00,0x80,0x00,0x2c,0x00,0x00,0x00,0x0f,0x50,0x00,0x20,0x00,0x40,0x02,0x80,0x00,0x04,0x00,0x8b,0xc0,0x58,0x00,0x22,0xc0,0x14,0x00,0x00,0x00,0x00,0x20,0x02,0xf0,0x00,0x68,0x0a,0x30,0x00,0x28,0x00,0xc0,0x00,0x10,0x02,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00 ],
-            },
-            enemy4 => SpriteData {
-                width: 16,
-                height: 16,
-                flags: 1,
-                data: vec![ 0x00,0x00,0x00,0x80,0x00,0x00,0x00,0x08,0x01,0x00,0x00,0x00,0x00,0x80,0x00,0x00,0x10,0x00,0x28,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x10,0x00,0x02,0x00,0x20,0x00,0x08,0x80,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x80,0x00,0xf0,0x01,0x00,0x08,0x00,0x00,0x00,0x00,0x00,0x00,0x40,0x02,0x00,0x00,0x00,0x00,0x00 ],
-            },
-            heart => SpriteData {
-                width: 8,
-                height: 8,
-                flags: 1,
-                data: vec![ 0x28,0xa0,0x96,0xa8,0x9a,0xa8,0xaa,0xac,0x2a,0xb0,0x0e,0xc0,0x03,0x00,0x00,0x00 ],
-            },
-            kite1 => SpriteData {
-                width: 16,
-                height: 16,
-                flags: 1,
-                data: vec![ 0x10,0x00,0x00,0x10,0x14,0xc0,0x0c,0x50,0x14,0x80,0x08,0x50,0x14,0xc0,0x0c,0x50,0x15,0xc0,0x0d,0x50,0x05,0x50,0x15,0x50,0x05,0x9a,0x99,0x40,0x06,0x6a,0xa6,0x40,0x01,0x9a,0x99,0x00,0x00,0x6a,0xa4,0x00,0x00,0xaa,0xa8,0x00,0x00,0x2a,0xa0,0x00,0x00,0x0a,0x80,0x00,0x00,0x0a,0x80,0x00,0x00,0x02,0x00,0x00,0x00,0x02,0x00,0x00 ],
-            },
-            kite2 => SpriteData {
-                width: 16,
-                height: 16,
-                flags: 1,
-                data: vec![ 0x40,0x00,0x00,0x04,0x50,0x00,0x00,0x14,0x50,0xc0,0x0c,0x14,0x50,0x80,0x08,0x14,0x54,0xc0,0x0c,0x54,0x15,0x60,0x25,0x54,0x16,0x6a,0xa6,0x50,0x19,0xaa,0xa9,0x90,0x06,0x6a,0xa6,0x40,0x01,0xaa,0xa9,0x00,0x00,0xaa,0xa8,0x00,0x00,0x2a,0xa0,0x00,0x00,0x0a,0x80,0x00,0x00,0x0a,0x80,0x00,0x00,0x02,0x00,0x00,0x00,0x02,0x00,0x00 ],
-            },
-            land => SpriteData {
-                width: 32,
-                height: 16,
-                flags: 1,
-                data: vec![ 0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x3f,0xf0,0x00,0x00,0x00,0x00,0x00,0x00,0xff,0xfc,0x00,0x00,0x00,0x03,0xc0,0x00,0xff,0xff,0x00,0x00,0x00,0x03,0xc0,0x03,0xff,0xff,0xc0,0x0f,0xff,0xcf,0xf0,0x03,0xff,0xff,0xf0,0x3f,0xff,0xff,0xf0,0xaa,0xaa,0xaa,0xaa,0xaa,0xaa,0xaa,0xaa,0x2a,0xaa,0xaa,0xaa,0xaa,0xaa,0xaa,0xa8,0x0a,0xaa,0xaa,0xaa,0xaa,0xaa,0xaa,0xa0,0x00,0xaa,0xaa,0xaa,0xaa,0xaa,0xaa,0x00,0x00,0x0a,0xaa,0xaa,0xaa,0xaa,0xa0,0x00,0x00,0x00,0xaa,0xaa,0xaa,0xaa,0x00,0x00,0x00,0x00,0x00,0xaa,0xaa,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00 ],
-            },
-            moon => SpriteData {
-                width: 8,
-                height: 8,
-                flags: 1,
-                data: vec![ 0x01,0x50,0x00,0x14,0x00,0x05,0x40,0x05,0x40,0x05,0x50,0x15,0x15,0x54,0x05,0x50 ],
-            },
-            ship1 => SpriteData {
-                width: 16,
-                height: 16,
-                flags: 1,
-                data: vec![ 0x00,0x01,0x00,0x00,0x00,0x01,0x00,0x00,0x00,0x01,0x00,0x00,0x00,0x05,0x80,0x00,0x00,0x05,0x80,0x00,0x00,0x15,0xa0,0x00,0x00,0x16,0xa0,0x00,0x00,0x56,0xa8,0x00,0x00,0x55,0xa8,0x00,0x00,0x55,0xa8,0x00,0x01,0x55,0xaa,0x00,0x05,0x56,0x6a,0x80,0x15,0x99,0xab,0xb0,0x56,0x66,0x6e,0xec,0x58,0x09,0x80,0xbc,0x60,0x00,0x00,0x3c ],
-            },
-            ship2 => SpriteData {
-                width: 16,
-                height: 16,
-                flags: 1,
-                data: vec![ 0x00,0x01,0x00,0x00,0x00,0x01,0x00,0x00,0x00,0x01,0x00,0x00,0x00,0x05,0x80,0x00,0x00,0x05,0x80,0x00,0x00,0x16,0xa0,0x00,0x00,0x16,0xa0,0x00,0x00,0x5a,0xa8,0x00,0x00,0x5a,0xac,0x00,0x00,0x5a,0xbc,0x00,0x01,0x5a,0xaf,0x00,0x01,0x66,0xbf,0x00,0x05,0x9a,0xaf,0xc0,0x15,0x66,0xbf,0xf0,0x16,0x0a,0xc3,0xf0,0x18,0x00,0x00,0xf0 ],
-            },
-            ship3 => SpriteData {
-                width: 16,
-                height: 16,
-                flags: 1,
-                data: vec![ 0x00,0x01,0x00,0x00,0x00,0x01,0x00,0x00,0x00,0x01,0x00,0x00,0x00,0x05,0x80,0x00,0x00,0x06,0x80,0x00,0x00,0x16,0xb0,0x00,0x00,0x1a,0xb0,0x00,0x00,0x1a,0xf0,0x00,0x00,0x5a,0xfc,0x00,0x00,0x6b,0xfc,0x00,0x00,0x6b,0xfc,0x00,0x01,0xae,0xff,0x00,0x01,0xab,0xff,0x00,0x06,0xae,0xff,0xc0,0x06,0x8b,0xcf,0xc0,0x06,0x00,0x03,0xc0 ],
-            },
-            ship4 => SpriteData {
-                width: 16,
-                height: 16,
-                flags: 1,
-                data: vec![ 0x00,0x01,0x00,0x00,0x00,0x01,0x00,0x00,0x00,0x01,0x00,0x00,0x00,0x05,0x40,0x00,0x00,0x05,0x40,0x00,0x00,0x15,0x60,0x00,0x00,0x15,0xa0,0x00,0x00,0x55,0xa8,0x00,0x00,0x55,0xa8,0x00,0x00,0x55,0x68,0x00,0x01,0x55,0xaa,0x00,0x01,0x56,0x6a,0x00,0x05,0x55,0x9a,0xc0,0x15,0x56,0x6e,0xb0,0x16,0x09,0x82,0xe0,0x18,0x00,0x00,0xb0 ],
-            },
-            ship5 => SpriteData {
-                width: 16,
-                height: 16,
-                flags: 1,
-                data: vec![ 0x00,0x01,0x00,0x00,0x00,0x01,0x00,0x00,0x00,0x01,0x00,0x00,0x00,0x05,0x40,0x00,0x00,0x05,0x40,0x00,0x00,0x15,0x50,0x00,0x00,0x15,0x50,0x00,0x00,0x15,0x60,0x00,0x00,0x55,0x58,0x00,0x00,0x55,0x68,0x00,0x00,0x55,0x98,0x00,0x01,0x55,0x6a,0x00,0x01,0x55,0x9a,0x00,0x05,0x56,0x6a,0x80,0x05,0x45,0x8a,0xc0,0x05,0x00,0x02,0xc0 ],
-            },
-            ship6 => SpriteData {
-                width: 16,
-                height: 16,
-                flags: 1,
-                data: vec![ 0x00,0x00,0x40,0x00,0x00,0x00,0x40,0x00,0x00,0x00,0x40,0x00,0x00,0x01,0x40,0x00,0x00,0x01,0x60,0x00,0x00,0x05,0x60,0x00,0x00,0x01,0xa8,0x00,0x00,0x40,0xaa,0x00,0x00,0x14,0x2a,0x00,0x00,0x15,0x00,0x00,0x00,0x55,0xa2,0x00,0x01,0x55,0x62,0x80,0x15,0x86,0x4b,0xa0,0x19,0x05,0x8e,0xf8,0x18,0x08,0x00,0x3c,0x40,0x00,0x00,0x0c ],
-            },
-            ship7 => SpriteData {
-                width: 16,
-                height: 16,
-                flags: 1,
-                data: vec![ 0x00,0x00,0x40,0x00,0x00,0x00,0x40,0x00,0x00,0x00,0x60,0x00,0x00,0x01,0x60,0x00,0x00,0x05,0x40,0x00,0x00,0x04,0x82,0x00,0x00,0x00,0x02,0x00,0x00,0x10,0x0a,0x80,0x00,0x05,0x0a,0x00,0x00,0x01,0x40,0x00,0x00,0x15,0x00,0x80,0x15,0x05,0xa0,0xa0,0x16,0x00,0x62,0xe0,0x10,0x01,0x83,0x8b,0x60,0x06,0x00,0x0c,0x00,0x08,0x00,0x0c ],
-            },
-            ship8 => SpriteData {
-                width: 16,
-                height: 16,
-                flags: 1,
-                data: vec![ 0x00,0x00,0x00,0x00,0x00,0x00,0x20,0x00,0x00,0x00,0x60,0x00,0x00,0x04,0x40,0x00,0x00,0x04,0x82,0x00,0x00,0x00,0x00,0x00,0x00,0x40,0x00,0x00,0x00,0x10,0x02,0x20,0x00,0x05,0x00,0x80,0x00,0x44,0x00,0x00,0x01,0x00,0x00,0x20,0x12,0x04,0x80,0x08,0x00,0x00,0x40,0x00,0x60,0x00,0x82,0x00,0x00,0x04,0x03,0x8b,0x00,0x00,0x00,0x0c ],
-            },
-            ship9 => SpriteData {
-                width: 16,
-                height: 16,
-                flags: 1,
-                data: vec![ 0x00,0x02,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x04,0x00,0x80,0x00,0x00,0x00,0x00,0x01,0x00,0x04,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x20,0x01,0x01,0x00,0x00,0x00,0x00,0x00,0x00,0x40,0x00,0x08,0x00,0x00,0x80,0x00,0x20,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x04,0x02,0x00,0x00,0x00,0x00,0x08 ],
-            },
-            speed => SpriteData {
-                width: 8,
-                height: 8,
-                flags: 1,
-                data: vec![ 0x02,0xc0,0x0a,0xb0,0x29,0x6c,0xa9,0xab,0xe5,0xaf,0x3a,0xbc,0x0e,0xf0,0x03,0xc0 ],
-            },
-            spreader => SpriteData {
-                width: 8,
-                height: 8,
-                flags: 1,
-                data: vec![ 0x2d,0x4b,0xee,0x7b,0xb8,0x2e,0x35,0x1c,0x06,0xd4,0x0e,0xe0,0x0b,0x80,0x03,0x00 ],
-            },
-            sun => SpriteData {
-                width: 8,
-                height: 8,
-                flags: 1,
-                data: vec![ 0x05,0x50,0x15,0x54,0x55,0x55,0x55,0x55,0x55,0x55,0x55,0x55,0x15,0x54,0x05,0x50 ],
-            },
-            turret1 => SpriteData {
-                width: 16,
-                height: 16,
-                flags: 1,
-                data: vec![ 0x02,0x80,0x0a,0x00,0x03,0xc0,0x0f,0x00,0x02,0x80,0x0a,0x00,0x02,0x96,0xfa,0x00,0x21,0x6a,0xaf,0x30,0xa1,0xaa,0xab,0x3c,0xad,0xaa,0xab,0xfc,0xad,0xaa,0xab,0xfc,0xad,0x6a,0xaf,0xfc,0xae,0x9a,0xba,0xfc,0xa2,0x96,0xfa,0x3c,0xa3,0xc0,0x0f,0x3c,0xa2,0x80,0x0a,0x2c,0x90,0x00,0x00,0x18,0x10,0x00,0x00,0x10,0x10,0x00,0x00,0x10 ],
-            },
-            turret2 => SpriteData {
-                width: 16,
-                height: 16,
-                flags: 1,
-                data: vec![ 0x02,0x80,0x0a,0x00,0x02,0x80,0x0a,0x00,0x03,0xc0,0x0f,0x00,0x02,0x96,0xfa,0x00,0x21,0x6a,0xaf,0x30,0xa1,0xaa,0xab,0x3c,0xad,0xaa,0xab,0xfc,0xad,0xaa,0xab,0xfc,0xad,0x6a,0xaf,0xfc,0xaf,0xda,0xbf,0xfc,0xa2,0x96,0xfa,0x3c,0xa2,0x80,0x0a,0x3c,0xa3,0xc0,0x0f,0x2c,0x90,0x00,0x00,0x18,0x10,0x00,0x00,0x10,0x10,0x00,0x00,0x10 ],
-            },
-            turret3 => SpriteData {
-                width: 16,
-                height: 16,
-                flags: 1,
-                data: vec![ 0x03,0xc0,0x0f,0x00,0x02,0x80,0x0a,0x00,0x02,0x80,0x0a,0x00,0x03,0xd6,0xff,0x00,0x21,0x6a,0xaf,0x30,0xa1,0xaa,0xab,0x3c,0xad,0xaa,0xab,0xfc,0xad,0xaa,0xab,0xfc,0xad,0x6a,0xaf,0xfc,0xae,0x9a,0xba,0xfc,0xa3,0xd6,0xff,0x3c,0xa2,0x80,0x0a,0x3c,0xa2,0x80,0x0a,0x2c,0x90,0x00,0x00,0x18,0x10,0x00,0x00,0x10,0x10,0x00,0x00,0x10 ],
-            },
-            turret4 => SpriteData {
-                width: 16,
-                height: 16,
-                flags: 1,
-                data: vec![ 0x02,0x80,0x0a,0x00,0x03,0xc0,0x0f,0x00,0x22,0x80,0x0a,0x30,0xa2,0x96,0xfa,0x3c,0xad,0x6a,0xaf,0xfc,0xad,0xaa,0xab,0xfc,0xad,0xaa,0xab,0xfc,0xad,0xaa,0xab,0xfc,0xa1,0x6a,0xaf,0x3c,0xa2,0x9a,0xba,0x3c,0xa2,0x96,0xfa,0x2c,0x93,0xc0,0x0f,0x18,0x12,0x80,0x0a,0x10,0x10,0x00,0x00,0x10,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00 ],
-            },
-            turret5 => SpriteData {
-                width: 16,
-                height: 16,
-                flags: 1,
-                data: vec![ 0x02,0x80,0x0a,0x00,0x03,0xc0,0x0f,0x00,0x02,0x80,0x0a,0x00,0x02,0x96,0xfa,0x00,0x21,0x6a,0xaf,0x30,0xa1,0xaa,0xbf,0x3c,0xad,0xaa,0xbb,0xfc,0xad,0xaa,0xbf,0xfc,0xad,0x6a,0xff,0xcc,0xae,0x9b,0xb2,0xc0,0xa2,0x96,0xc0,0x00,0xa3,0xc0,0x00,0x00,0xa0,0x00,0x00,0x00,0x90,0x00,0x00,0x00,0x10,0x00,0x00,0x00,0x10,0x00,0x00,0x00 ],
-            },
-            turret6 => SpriteData {
-                width: 16,
-                height: 16,
-                flags: 1,
-                data: vec![ 0x02,0x80,0x0a,0x00,0x03,0xc0,0x0f,0x00,0x22,0x80,0x0a,0x00,0xa2,0x96,0xfa,0x00,0xad,0x6a,0xaf,0x30,0xad,0xaa,0xbf,0x3c,0xad,0xaa,0xbb,0xfc,0xad,0xaa,0xbf,0xfc,0xa1,0x6a,0xff,0xcc,0xa2,0x9b,0xb2,0xc0,0xa2,0x96,0xc0,0x00,0x93,0xc0,0x00,0x00,0x10,0x00,0x00,0x00,0x10,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00 ],
-            },
-            turret7 => SpriteData {
-                width: 16,
-                height: 16,
-                flags: 1,
-                data: vec![ 0x0c,0x00,0x08,0x00,0x0a,0x00,0x30,0x00,0x0a,0x00,0x08,0x00,0x04,0x02,0xe8,0x00,0x00,0x02,0x33,0x20,0x80,0x22,0xa0,0x2c,0xad,0xa2,0x80,0xf0,0xad,0xa2,0x83,0xc0,0xa1,0xa0,0x03,0x00,0x02,0x20,0xb8,0x00,0x80,0x90,0xe0,0x0c,0x80,0xc0,0x0c,0x03,0x20,0x00,0x08,0x00,0x24,0x00,0x00,0x02,0x04,0x00,0x00,0x00,0x04,0x00,0x00,0x04 ],
-            },
-            turret8 => SpriteData {
-                width: 16,
-                height: 16,
-                flags: 1,
-                data: vec![ 0x0c,0x00,0x00,0x00,0x02,0x00,0x08,0x00,0x08,0x00,0x30,0x00,0x04,0x02,0x08,0x20,0x00,0x22,0x0c,0x2c,0x20,0x00,0x00,0xf0,0x28,0x40,0x00,0xc0,0x00,0x03,0xb0,0x00,0x08,0x00,0x30,0x30,0x00,0x88,0x03,0x3c,0x08,0x00,0x00,0x00,0x08,0x24,0x0c,0x00,0x00,0x30,0x08,0x18,0x00,0x00,0x00,0x10,0x00,0x40,0x00,0x10,0x00,0x40,0x00,0x00 ],
-            },
-            turret9 => SpriteData {
-                width: 16,
-                height: 16,
-                flags: 1,
-                data: vec![ 0x00,0x00,0x00,0x00,0x03,0x00,0x08,0x00,0x00,0x80,0x30,0x00,0x00,0x00,0x00,0xb0,0x08,0x00,0x00,0x00,0x00,0x10,0x03,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0xc0,0x00,0x00,0x20,0x00,0x30,0x02,0x00,0x00,0x00,0x02,0x00,0x00,0x00,0x00,0x00,0x00,0x40,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x40,0x00,0x00,0x00,0x00,0x00,0x00 ],
-            },
+/// The data and opacity mask for every sprite, in the order they are
+/// declared.  Kept in one static so each sprite's bytes are in the binary
+/// exactly once.
+static SPRITES: &[SpriteData] = &[
+    {
+        const DATA: &[u8] = &[ 0x3c,0x00,0x3f,0x00,0x3f,0xc0,0x3f,0xf0,0x3f,0xf0,0x3f,0xc0,0x3f,0x00,0x3c,0x00 ];
+        SpriteData {
+            width: 8,
+            height: 8,
+            flags: 1,
+            data: DATA,
+            mask: &mask::<8>(DATA, 8),
+        }
+    },
+    {
+        const DATA: &[u8] = &[ 0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x0f,0xff,0xff,0xf0,0x00,0x00,0x00,0x00,0xfa,0xaa,0xaa,0xaf,0x00,0x00,0x00,0x0f,0xa5,0x55,0x55,0x5a,0xf0,0x00,0x00,0xfa,0x55,0x55,0x55,0x55,0xaf,0x00,0x0f,0xa5,0x5f,0x55,0x55,0xf5,0x5a,0xf0,0x3a,0x55,0x7a,0xd5,0x57,0xad,0x55,0xac,0xe5,0x55,0x7a,0xd5,0x57,0xad,0x55,0x5b,0xe5,0x55,0x5f,0x55,0x55,0xf5,0x55,0x5b,0xe9,0x55,0x55,0x5a,0xa5,0x55,0x55,0x6b,0x3a,0xa5,0x55,0x6f,0xf9,0x55,0x5a,0xac,0x0f,0xea,0xaa,0xbc,0x3e,0xaa,0xab,0xf0,0x00,0xfb,0xfa,0xc0,0x03,0xaf,0xef,0x00,0x00,0xec,0x3b,0x00,0x00,0xec,0x3b,0x00,0x00,0xec,0x0c,0x00,0x00,0x30,0x3b,0x00,0x00,0xdc,0x00,0x00,0x00,0x00,0x37,0x00,0x00,0xdc,0x00,0x00,0x00,0x00,0x37,0x00,0x00,0x30,0x00,0x00,0x00,0x00,0x0c,0x00 ];
+        SpriteData {
+            width: 32,
+            height: 18,
+            flags: 1,
+            data: DATA,
+            mask: &mask::<18>(DATA, 32),
+        }
+    },
+    {
+        const DATA: &[u8] = &[ 0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x0f,0xff,0xff,0xf0,0x00,0x00,0x00,0x00,0xfa,0xaa,0xaa,0xaf,0x00,0x00,0x00,0x0f,0xa5,0x55,0x55,0x5a,0xf0,0x00,0x00,0xfa,0x55,0x55,0x55,0x55,0xaf,0x00,0x0f,0xa5,0x5f,0x55,0x55,0xf5,0x5a,0xf0,0x3a,0x55,0x75,0xd5,0x57,0x5d,0x55,0xac,0xe5,0x55,0x75,0xd5,0x57,0x5d,0x55,0x5b,0xe5,0x55,0x5f,0x55,0x55,0xf5,0x55,0x5b,0xe9,0x55,0x55,0x5a,0xa5,0x55,0x55,0x6b,0x3a,0xa5,0x55,0x6f,0xf9,0x55,0x5a,0xac,0x0f,0xea,0xaa,0xbc,0x3e,0xaa,0xab,0xf0,0x00,0xfb,0xf5,0xc0,0x03,0x5f,0xef,0x00,0x00,0xec,0x3b,0x00,0x00,0xec,0x3b,0x00,0x00,0xec,0x0c,0x00,0x00,0x30,0x3b,0x00,0x00,0xec,0x00,0x00,0x00,0x00,0x3b,0x00,0x00,0xec,0x00,0x00,0x00,0x00,0x3b,0x00,0x00,0x30,0x00,0x00,0x00,0x00,0x0c,0x00 ];
+        SpriteData {
+            width: 32,
+            height: 18,
+            flags: 1,
+            data: DATA,
+            mask: &mask::<18>(DATA, 32),
+        }
+    },
+    {
+        const DATA: &[u8] = &[ 0x28,0xac,0xe8,0x30 ];
+        SpriteData {
+            width: 4,
+            height: 4,
+            flags: 1,
+            data: DATA,
+            mask: &mask::<4>(DATA, 4),
+        }
+    },
+    {
+        const DATA: &[u8] = &[ 0x20,0xb8,0xe8,0x38 ];
+        SpriteData {
+            width: 4,
+            height: 4,
+            flags: 1,
+            data: DATA,
+            mask: &mask::<4>(DATA, 4),
+        }
+    },
+    {
+        const DATA: &[u8] = &[ 0x20,0xae,0x3b,0x2c ];
+        SpriteData {
+            width: 4,
+            height: 4,
+            flags: 1,
+            data: DATA,
+            mask: &mask::<4>(DATA, 4),
+        }
+    },
+    {
+        const DATA: &[u8] = &[ 0x28,0xee,0xb8,0x20 ];
+        SpriteData {
+            width: 4,
+            height: 4,
+            flags: 1,
+            data: DATA,
+            mask: &mask::<4>(DATA, 4),
+        }
+    },
+    {
+        const DATA: &[u8] = &[ 0x00,0x40,0x08,0x00,0x01,0x40,0x0a,0x00,0x05,0x40,0x0b,0x80,0x05,0x80,0x0a,0xc0,0x16,0x60,0x3b,0xf0,0x15,0xaa,0xae,0xf0,0x16,0xaa,0xab,0xf0,0x15,0xaa,0xaa,0xf0,0x16,0x6a,0xab,0xf0,0x15,0xa8,0xbb,0xf0,0x05,0x68,0xaf,0xc0,0x01,0x68,0xaf,0x00,0x00,0x50,0x2c,0x00,0x00,0x10,0x20,0x00,0x00,0x10,0x20,0x00,0x00,0x10,0x20,0x00 ];
+        SpriteData {
+            width: 16,
+            height: 16,
+            flags: 1,
+            data: DATA,
+            mask: &mask::<16>(DATA, 16),
+        }
+    },
+    {
+        const DATA: &[u8] = &[ 0x00,0x00,0x02,0x00,0x00,0x40,0x02,0x00,0x00,0x40,0x02,0x20,0x01,0x40,0x02,0xb0,0x05,0xa0,0x00,0x3c,0x15,0x00,0x00,0x00,0x10,0x02,0xac,0x00,0x01,0x0a,0xab,0xc0,0x16,0x6a,0xaa,0xc0,0x05,0xa8,0x80,0x00,0x01,0x68,0x0b,0xc0,0x01,0x68,0x2b,0xc0,0x00,0x50,0x0b,0x00,0x00,0x10,0x08,0x00,0x00,0x10,0x00,0x00,0x00,0x00,0x00,0x00 ];
+        SpriteData {
+            width: 16,
+            height: 16,
+            flags: 1,
+            data: DATA,
+            mask: &mask::<16>(DATA, 16),
+        }
+    },
+    {
+        const DATA: &[u8] = &[ 0x01,0x00,0x00,0x00,0x01,0x00,0x00,0x80,0x01,0x00,0x00,0x08,0x00,0x80,0x00,0x2c,0x00,0x00,0x00,0x0f,0x50,0x00,0x20,0x00,0x40,0x02,0x80,0x00,0x04,0x00,0x8b,0xc0,0x58,0x00,0x22,0xc0,0x14,0x00,0x00,0x00,0x00,0x20,0x02,0xf0,0x00,0x68,0x0a,0x30,0x00,0x28,0x00,0xc0,0x00,0x10,0x02,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00 ];
+        SpriteData {
+            width: 16,
+            height: 16,
+            flags: 1,
+            data: DATA,
+            mask: &mask::<16>(DATA, 16),
+        }
+    },
+    {
+        const DATA: &[u8] = &[ 0x00,0x00,0x00,0x80,0x00,0x00,0x00,0x08,0x01,0x00,0x00,0x00,0x00,0x80,0x00,0x00,0x10,0x00,0x28,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x10,0x00,0x02,0x00,0x20,0x00,0x08,0x80,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x80,0x00,0xf0,0x01,0x00,0x08,0x00,0x00,0x00,0x00,0x00,0x00,0x40,0x02,0x00,0x00,0x00,0x00,0x00 ];
+        SpriteData {
+            width: 16,
+            height: 16,
+            flags: 1,
+            data: DATA,
+            mask: &mask::<16>(DATA, 16),
+        }
+    },
+    {
+        const DATA: &[u8] = &[ 0x28,0xa0,0x96,0xa8,0x9a,0xa8,0xaa,0xac,0x2a,0xb0,0x0e,0xc0,0x03,0x00,0x00,0x00 ];
+        SpriteData {
+            width: 8,
+            height: 8,
+            flags: 1,
+            data: DATA,
+            mask: &mask::<8>(DATA, 8),
+        }
+    },
+    {
+        const DATA: &[u8] = &[ 0x10,0x00,0x00,0x10,0x14,0xc0,0x0c,0x50,0x14,0x80,0x08,0x50,0x14,0xc0,0x0c,0x50,0x15,0xc0,0x0d,0x50,0x05,0x50,0x15,0x50,0x05,0x9a,0x99,0x40,0x06,0x6a,0xa6,0x40,0x01,0x9a,0x99,0x00,0x00,0x6a,0xa4,0x00,0x00,0xaa,0xa8,0x00,0x00,0x2a,0xa0,0x00,0x00,0x0a,0x80,0x00,0x00,0x0a,0x80,0x00,0x00,0x02,0x00,0x00,0x00,0x02,0x00,0x00 ];
+        SpriteData {
+            width: 16,
+            height: 16,
+            flags: 1,
+            data: DATA,
+            mask: &mask::<16>(DATA, 16),
+        }
+    },
+    {
+        const DATA: &[u8] = &[ 0x40,0x00,0x00,0x04,0x50,0x00,0x00,0x14,0x50,0xc0,0x0c,0x14,0x50,0x80,0x08,0x14,0x54,0xc0,0x0c,0x54,0x15,0x60,0x25,0x54,0x16,0x6a,0xa6,0x50,0x19,0xaa,0xa9,0x90,0x06,0x6a,0xa6,0x40,0x01,0xaa,0xa9,0x00,0x00,0xaa,0xa8,0x00,0x00,0x2a,0xa0,0x00,0x00,0x0a,0x80,0x00,0x00,0x0a,0x80,0x00,0x00,0x02,0x00,0x00,0x00,0x02,0x00,0x00 ];
+        SpriteData {
+            width: 16,
+            height: 16,
+            flags: 1,
+            data: DATA,
+            mask: &mask::<16>(DATA, 16),
+        }
+    },
+    {
+        const DATA: &[u8] = &[ 0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x3f,0xf0,0x00,0x00,0x00,0x00,0x00,0x00,0xff,0xfc,0x00,0x00,0x00,0x03,0xc0,0x00,0xff,0xff,0x00,0x00,0x00,0x03,0xc0,0x03,0xff,0xff,0xc0,0x0f,0xff,0xcf,0xf0,0x03,0xff,0xff,0xf0,0x3f,0xff,0xff,0xf0,0xaa,0xaa,0xaa,0xaa,0xaa,0xaa,0xaa,0xaa,0x2a,0xaa,0xaa,0xaa,0xaa,0xaa,0xaa,0xa8,0x0a,0xaa,0xaa,0xaa,0xaa,0xaa,0xaa,0xa0,0x00,0xaa,0xaa,0xaa,0xaa,0xaa,0xaa,0x00,0x00,0x0a,0xaa,0xaa,0xaa,0xaa,0xa0,0x00,0x00,0x00,0xaa,0xaa,0xaa,0xaa,0x00,0x00,0x00,0x00,0x00,0xaa,0xaa,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00 ];
+        SpriteData {
+            width: 32,
+            height: 16,
+            flags: 1,
+            data: DATA,
+            mask: &mask::<16>(DATA, 32),
+        }
+    },
+    {
+        const DATA: &[u8] = &[ 0x01,0x50,0x00,0x14,0x00,0x05,0x40,0x05,0x40,0x05,0x50,0x15,0x15,0x54,0x05,0x50 ];
+        SpriteData {
+            width: 8,
+            height: 8,
+            flags: 1,
+            data: DATA,
+            mask: &mask::<8>(DATA, 8),
+        }
+    },
+    {
+        const DATA: &[u8] = &[ 0x00,0x01,0x00,0x00,0x00,0x01,0x00,0x00,0x00,0x01,0x00,0x00,0x00,0x05,0x80,0x00,0x00,0x05,0x80,0x00,0x00,0x15,0xa0,0x00,0x00,0x16,0xa0,0x00,0x00,0x56,0xa8,0x00,0x00,0x55,0xa8,0x00,0x00,0x55,0xa8,0x00,0x01,0x55,0xaa,0x00,0x05,0x56,0x6a,0x80,0x15,0x99,0xab,0xb0,0x56,0x66,0x6e,0xec,0x58,0x09,0x80,0xbc,0x60,0x00,0x00,0x3c ];
+        SpriteData {
+            width: 16,
+            height: 16,
+            flags: 1,
+            data: DATA,
+            mask: &mask::<16>(DATA, 16),
+        }
+    },
+    {
+        const DATA: &[u8] = &[ 0x00,0x01,0x00,0x00,0x00,0x01,0x00,0x00,0x00,0x01,0x00,0x00,0x00,0x05,0x80,0x00,0x00,0x05,0x80,0x00,0x00,0x16,0xa0,0x00,0x00,0x16,0xa0,0x00,0x00,0x5a,0xa8,0x00,0x00,0x5a,0xac,0x00,0x00,0x5a,0xbc,0x00,0x01,0x5a,0xaf,0x00,0x01,0x66,0xbf,0x00,0x05,0x9a,0xaf,0xc0,0x15,0x66,0xbf,0xf0,0x16,0x0a,0xc3,0xf0,0x18,0x00,0x00,0xf0 ];
+        SpriteData {
+            width: 16,
+            height: 16,
+            flags: 1,
+            data: DATA,
+            mask: &mask::<16>(DATA, 16),
+        }
+    },
+    {
+        const DATA: &[u8] = &[ 0x00,0x01,0x00,0x00,0x00,0x01,0x00,0x00,0x00,0x01,0x00,0x00,0x00,0x05,0x80,0x00,0x00,0x06,0x80,0x00,0x00,0x16,0xb0,0x00,0x00,0x1a,0xb0,0x00,0x00,0x1a,0xf0,0x00,0x00,0x5a,0xfc,0x00,0x00,0x6b,0xfc,0x00,0x00,0x6b,0xfc,0x00,0x01,0xae,0xff,0x00,0x01,0xab,0xff,0x00,0x06,0xae,0xff,0xc0,0x06,0x8b,0xcf,0xc0,0x06,0x00,0x03,0xc0 ];
+        SpriteData {
+            width: 16,
+            height: 16,
+            flags: 1,
+            data: DATA,
+            mask: &mask::<16>(DATA, 16),
         }
+    },
+    {
+        const DATA: &[u8] = &[ 0x00,0x01,0x00,0x00,0x00,0x01,0x00,0x00,0x00,0x01,0x00,0x00,0x00,0x05,0x40,0x00,0x00,0x05,0x40,0x00,0x00,0x15,0x60,0x00,0x00,0x15,0xa0,0x00,0x00,0x55,0xa8,0x00,0x00,0x55,0xa8,0x00,0x00,0x55,0x68,0x00,0x01,0x55,0xaa,0x00,0x01,0x56,0x6a,0x00,0x05,0x55,0x9a,0xc0,0x15,0x56,0x6e,0xb0,0x16,0x09,0x82,0xe0,0x18,0x00,0x00,0xb0 ];
+        SpriteData {
+            width: 16,
+            height: 16,
+            flags: 1,
+            data: DATA,
+            mask: &mask::<16>(DATA, 16),
+        }
+    },
+    {
+        const DATA: &[u8] = &[ 0x00,0x01,0x00,0x00,0x00,0x01,0x00,0x00,0x00,0x01,0x00,0x00,0x00,0x05,0x40,0x00,0x00,0x05,0x40,0x00,0x00,0x15,0x50,0x00,0x00,0x15,0x50,0x00,0x00,0x15,0x60,0x00,0x00,0x55,0x58,0x00,0x00,0x55,0x68,0x00,0x00,0x55,0x98,0x00,0x01,0x55,0x6a,0x00,0x01,0x55,0x9a,0x00,0x05,0x56,0x6a,0x80,0x05,0x45,0x8a,0xc0,0x05,0x00,0x02,0xc0 ];
+        SpriteData {
+            width: 16,
+            height: 16,
+            flags: 1,
+            data: DATA,
+            mask: &mask::<16>(DATA, 16),
+        }
+    },
+    {
+        const DATA: &[u8] = &[ 0x00,0x00,0x40,0x00,0x00,0x00,0x40,0x00,0x00,0x00,0x40,0x00,0x00,0x01,0x40,0x00,0x00,0x01,0x60,0x00,0x00,0x05,0x60,0x00,0x00,0x01,0xa8,0x00,0x00,0x40,0xaa,0x00,0x00,0x14,0x2a,0x00,0x00,0x15,0x00,0x00,0x00,0x55,0xa2,0x00,0x01,0x55,0x62,0x80,0x15,0x86,0x4b,0xa0,0x19,0x05,0x8e,0xf8,0x18,0x08,0x00,0x3c,0x40,0x00,0x00,0x0c ];
+        SpriteData {
+            width: 16,
+            height: 16,
+            flags: 1,
+            data: DATA,
+            mask: &mask::<16>(DATA, 16),
+        }
+    },
+    {
+        const DATA: &[u8] = &[ 0x00,0x00,0x40,0x00,0x00,0x00,0x40,0x00,0x00,0x00,0x60,0x00,0x00,0x01,0x60,0x00,0x00,0x05,0x40,0x00,0x00,0x04,0x82,0x00,0x00,0x00,0x02,0x00,0x00,0x10,0x0a,0x80,0x00,0x05,0x0a,0x00,0x00,0x01,0x40,0x00,0x00,0x15,0x00,0x80,0x15,0x05,0xa0,0xa0,0x16,0x00,0x62,0xe0,0x10,0x01,0x83,0x8b,0x60,0x06,0x00,0x0c,0x00,0x08,0x00,0x0c ];
+        SpriteData {
+            width: 16,
+            height: 16,
+            flags: 1,
+            data: DATA,
+            mask: &mask::<16>(DATA, 16),
+        }
+    },
+    {
+        const DATA: &[u8] = &[ 0x00,0x00,0x00,0x00,0x00,0x00,0x20,0x00,0x00,0x00,0x60,0x00,0x00,0x04,0x40,0x00,0x00,0x04,0x82,0x00,0x00,0x00,0x00,0x00,0x00,0x40,0x00,0x00,0x00,0x10,0x02,0x20,0x00,0x05,0x00,0x80,0x00,0x44,0x00,0x00,0x01,0x00,0x00,0x20,0x12,0x04,0x80,0x08,0x00,0x00,0x40,0x00,0x60,0x00,0x82,0x00,0x00,0x04,0x03,0x8b,0x00,0x00,0x00,0x0c ];
+        SpriteData {
+            width: 16,
+            height: 16,
+            flags: 1,
+            data: DATA,
+            mask: &mask::<16>(DATA, 16),
+        }
+    },
+    {
+        const DATA: &[u8] = &[ 0x00,0x02,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x04,0x00,0x80,0x00,0x00,0x00,0x00,0x01,0x00,0x04,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x20,0x01,0x01,0x00,0x00,0x00,0x00,0x00,0x00,0x40,0x00,0x08,0x00,0x00,0x80,0x00,0x20,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x04,0x02,0x00,0x00,0x00,0x00,0x08 ];
+        SpriteData {
+            width: 16,
+            height: 16,
+            flags: 1,
+            data: DATA,
+            mask: &mask::<16>(DATA, 16),
+        }
+    },
+    {
+        const DATA: &[u8] = &[ 0x02,0xc0,0x0a,0xb0,0x29,0x6c,0xa9,0xab,0xe5,0xaf,0x3a,0xbc,0x0e,0xf0,0x03,0xc0 ];
+        SpriteData {
+            width: 8,
+            height: 8,
+            flags: 1,
+            data: DATA,
+            mask: &mask::<8>(DATA, 8),
+        }
+    },
+    {
+        const DATA: &[u8] = &[ 0x2d,0x4b,0xee,0x7b,0xb8,0x2e,0x35,0x1c,0x06,0xd4,0x0e,0xe0,0x0b,0x80,0x03,0x00 ];
+        SpriteData {
+            width: 8,
+            height: 8,
+            flags: 1,
+            data: DATA,
+            mask: &mask::<8>(DATA, 8),
+        }
+    },
+    {
+        const DATA: &[u8] = &[ 0x05,0x50,0x15,0x54,0x55,0x55,0x55,0x55,0x55,0x55,0x55,0x55,0x15,0x54,0x05,0x50 ];
+        SpriteData {
+            width: 8,
+            height: 8,
+            flags: 1,
+            data: DATA,
+            mask: &mask::<8>(DATA, 8),
+        }
+    },
+    {
+        const DATA: &[u8] = &[ 0x02,0x80,0x0a,0x00,0x03,0xc0,0x0f,0x00,0x02,0x80,0x0a,0x00,0x02,0x96,0xfa,0x00,0x21,0x6a,0xaf,0x30,0xa1,0xaa,0xab,0x3c,0xad,0xaa,0xab,0xfc,0xad,0xaa,0xab,0xfc,0xad,0x6a,0xaf,0xfc,0xae,0x9a,0xba,0xfc,0xa2,0x96,0xfa,0x3c,0xa3,0xc0,0x0f,0x3c,0xa2,0x80,0x0a,0x2c,0x90,0x00,0x00,0x18,0x10,0x00,0x00,0x10,0x10,0x00,0x00,0x10 ];
+        SpriteData {
+            width: 16,
+            height: 16,
+            flags: 1,
+            data: DATA,
+            mask: &mask::<16>(DATA, 16),
+        }
+    },
+    {
+        const DATA: &[u8] = &[ 0x02,0x80,0x0a,0x00,0x02,0x80,0x0a,0x00,0x03,0xc0,0x0f,0x00,0x02,0x96,0xfa,0x00,0x21,0x6a,0xaf,0x30,0xa1,0xaa,0xab,0x3c,0xad,0xaa,0xab,0xfc,0xad,0xaa,0xab,0xfc,0xad,0x6a,0xaf,0xfc,0xaf,0xda,0xbf,0xfc,0xa2,0x96,0xfa,0x3c,0xa2,0x80,0x0a,0x3c,0xa3,0xc0,0x0f,0x2c,0x90,0x00,0x00,0x18,0x10,0x00,0x00,0x10,0x10,0x00,0x00,0x10 ];
+        SpriteData {
+            width: 16,
+            height: 16,
+            flags: 1,
+            data: DATA,
+            mask: &mask::<16>(DATA, 16),
+        }
+    },
+    {
+        const DATA: &[u8] = &[ 0x03,0xc0,0x0f,0x00,0x02,0x80,0x0a,0x00,0x02,0x80,0x0a,0x00,0x03,0xd6,0xff,0x00,0x21,0x6a,0xaf,0x30,0xa1,0xaa,0xab,0x3c,0xad,0xaa,0xab,0xfc,0xad,0xaa,0xab,0xfc,0xad,0x6a,0xaf,0xfc,0xae,0x9a,0xba,0xfc,0xa3,0xd6,0xff,0x3c,0xa2,0x80,0x0a,0x3c,0xa2,0x80,0x0a,0x2c,0x90,0x00,0x00,0x18,0x10,0x00,0x00,0x10,0x10,0x00,0x00,0x10 ];
+        SpriteData {
+            width: 16,
+            height: 16,
+            flags: 1,
+            data: DATA,
+            mask: &mask::<16>(DATA, 16),
+        }
+    },
+    {
+        const DATA: &[u8] = &[ 0x02,0x80,0x0a,0x00,0x03,0xc0,0x0f,0x00,0x22,0x80,0x0a,0x30,0xa2,0x96,0xfa,0x3c,0xad,0x6a,0xaf,0xfc,0xad,0xaa,0xab,0xfc,0xad,0xaa,0xab,0xfc,0xad,0xaa,0xab,0xfc,0xa1,0x6a,0xaf,0x3c,0xa2,0x9a,0xba,0x3c,0xa2,0x96,0xfa,0x2c,0x93,0xc0,0x0f,0x18,0x12,0x80,0x0a,0x10,0x10,0x00,0x00,0x10,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00 ];
+        SpriteData {
+            width: 16,
+            height: 16,
+            flags: 1,
+            data: DATA,
+            mask: &mask::<16>(DATA, 16),
+        }
+    },
+    {
+        const DATA: &[u8] = &[ 0x02,0x80,0x0a,0x00,0x03,0xc0,0x0f,0x00,0x02,0x80,0x0a,0x00,0x02,0x96,0xfa,0x00,0x21,0x6a,0xaf,0x30,0xa1,0xaa,0xbf,0x3c,0xad,0xaa,0xbb,0xfc,0xad,0xaa,0xbf,0xfc,0xad,0x6a,0xff,0xcc,0xae,0x9b,0xb2,0xc0,0xa2,0x96,0xc0,0x00,0xa3,0xc0,0x00,0x00,0xa0,0x00,0x00,0x00,0x90,0x00,0x00,0x00,0x10,0x00,0x00,0x00,0x10,0x00,0x00,0x00 ];
+        SpriteData {
+            width: 16,
+            height: 16,
+            flags: 1,
+            data: DATA,
+            mask: &mask::<16>(DATA, 16),
+        }
+    },
+    {
+        const DATA: &[u8] = &[ 0x02,0x80,0x0a,0x00,0x03,0xc0,0x0f,0x00,0x22,0x80,0x0a,0x00,0xa2,0x96,0xfa,0x00,0xad,0x6a,0xaf,0x30,0xad,0xaa,0xbf,0x3c,0xad,0xaa,0xbb,0xfc,0xad,0xaa,0xbf,0xfc,0xa1,0x6a,0xff,0xcc,0xa2,0x9b,0xb2,0xc0,0xa2,0x96,0xc0,0x00,0x93,0xc0,0x00,0x00,0x10,0x00,0x00,0x00,0x10,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00 ];
+        SpriteData {
+            width: 16,
+            height: 16,
+            flags: 1,
+            data: DATA,
+            mask: &mask::<16>(DATA, 16),
+        }
+    },
+    {
+        const DATA: &[u8] = &[ 0x0c,0x00,0x08,0x00,0x0a,0x00,0x30,0x00,0x0a,0x00,0x08,0x00,0x04,0x02,0xe8,0x00,0x00,0x02,0x33,0x20,0x80,0x22,0xa0,0x2c,0xad,0xa2,0x80,0xf0,0xad,0xa2,0x83,0xc0,0xa1,0xa0,0x03,0x00,0x02,0x20,0xb8,0x00,0x80,0x90,0xe0,0x0c,0x80,0xc0,0x0c,0x03,0x20,0x00,0x08,0x00,0x24,0x00,0x00,0x02,0x04,0x00,0x00,0x00,0x04,0x00,0x00,0x04 ];
+        SpriteData {
+            width: 16,
+            height: 16,
+            flags: 1,
+            data: DATA,
+            mask: &mask::<16>(DATA, 16),
+        }
+    },
+    {
+        const DATA: &[u8] = &[ 0x0c,0x00,0x00,0x00,0x02,0x00,0x08,0x00,0x08,0x00,0x30,0x00,0x04,0x02,0x08,0x20,0x00,0x22,0x0c,0x2c,0x20,0x00,0x00,0xf0,0x28,0x40,0x00,0xc0,0x00,0x03,0xb0,0x00,0x08,0x00,0x30,0x30,0x00,0x88,0x03,0x3c,0x08,0x00,0x00,0x00,0x08,0x24,0x0c,0x00,0x00,0x30,0x08,0x18,0x00,0x00,0x00,0x10,0x00,0x40,0x00,0x10,0x00,0x40,0x00,0x00 ];
+        SpriteData {
+            width: 16,
+            height: 16,
+            flags: 1,
+            data: DATA,
+            mask: &mask::<16>(DATA, 16),
+        }
+    },
+    {
+        const DATA: &[u8] = &[ 0x00,0x00,0x00,0x00,0x03,0x00,0x08,0x00,0x00,0x80,0x30,0x00,0x00,0x00,0x00,0xb0,0x08,0x00,0x00,0x00,0x00,0x10,0x03,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0xc0,0x00,0x00,0x20,0x00,0x30,0x02,0x00,0x00,0x00,0x02,0x00,0x00,0x00,0x00,0x00,0x00,0x40,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x40,0x00,0x00,0x00,0x00,0x00,0x00 ];
+        SpriteData {
+            width: 16,
+            height: 16,
+            flags: 1,
+            data: DATA,
+            mask: &mask::<16>(DATA, 16),
+        }
+    },
+];
+
+impl Sprite {
+    /// Every sprite, in the order they are declared.
+    pub const ALL: &[Sprite] = &[
+        Sprite::arrow,
+        Sprite::boss1,
+        Sprite::boss2,
+        Sprite::bullet1,
+        Sprite::bullet2,
+        Sprite::bullet3,
+        Sprite::bullet4,
+        Sprite::enemy1,
+        Sprite::enemy2,
+        Sprite::enemy3,
+        Sprite::enemy4,
+        Sprite::heart,
+        Sprite::kite1,
+        Sprite::kite2,
+        Sprite::land,
+        Sprite::moon,
+        Sprite::ship1,
+        Sprite::ship2,
+        Sprite::ship3,
+        Sprite::ship4,
+        Sprite::ship5,
+        Sprite::ship6,
+        Sprite::ship7,
+        Sprite::ship8,
+        Sprite::ship9,
+        Sprite::speed,
+        Sprite::spreader,
+        Sprite::sun,
+        Sprite::turret1,
+        Sprite::turret2,
+        Sprite::turret3,
+        Sprite::turret4,
+        Sprite::turret5,
+        Sprite::turret6,
+        Sprite::turret7,
+        Sprite::turret8,
+        Sprite::turret9,
+    ];
+
+    /// The data for the sprite, which lives in the binary so handing it out
+    /// never allocates.
+    pub fn get(self) -> &'static SpriteData {
+        &SPRITES[self as usize]
     }
 }
 
-
@@ -0,0 +1,110 @@
+//! Numbers kept about a run for the end screen.
+
+use crate::{Cycle, EnemyKind};
+
+/// What a player got up to over a run.
+#[derive(Clone, Debug, Default)]
+pub struct Stats {
+    /// Bullets fired.
+    pub shots: u32,
+    /// Bullets that hit an enemy or the boss.
+    pub hits: u32,
+    /// Health lost to bullets and collisions.
+    pub damage_taken: u32,
+    pub powerups: u32,
+    /// Enemies shot down, by `EnemyKind`.
+    kills: [u32; 3],
+    pub bosses: u32,
+    /// Frames spent flying in the day and at night.
+    day_frames: u32,
+    night_frames: u32,
+}
+
+impl Stats {
+    pub fn add_kill(&mut self, kind: EnemyKind) {
+        self.kills[kind as usize] += 1;
+    }
+
+    pub fn kills(&self, kind: EnemyKind) -> u32 {
+        self.kills[kind as usize]
+    }
+
+    /// Every enemy and boss shot down.
+    pub fn total_kills(&self) -> u32 {
+        self.kills.iter().sum::<u32>() + self.bosses
+    }
+
+    /// Counts a frame spent flying in `cycle`.
+    pub fn add_frame(&mut self, cycle: Cycle) {
+        match cycle {
+            Cycle::Day => self.day_frames += 1,
+            Cycle::Night => self.night_frames += 1,
+        }
+    }
+
+    pub fn frames(&self, cycle: Cycle) -> u32 {
+        match cycle {
+            Cycle::Day => self.day_frames,
+            Cycle::Night => self.night_frames,
+        }
+    }
+
+    /// Percentage of shots that hit.
+    pub fn accuracy(&self) -> u32 {
+        (self.hits * 100).checked_div(self.shots).unwrap_or(0)
+    }
+
+    /// Everyone's stats together.  Counts are added up, and time is whoever
+    /// lasted longest.
+    pub fn combine(&self, other: &Stats) -> Stats {
+        Stats {
+            shots: self.shots + other.shots,
+            hits: self.hits + other.hits,
+            damage_taken: self.damage_taken + other.damage_taken,
+            powerups: self.powerups + other.powerups,
+            kills: core::array::from_fn(|i| self.kills[i] + other.kills[i]),
+            bosses: self.bosses + other.bosses,
+            day_frames: self.day_frames.max(other.day_frames),
+            night_frames: self.night_frames.max(other.night_frames),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accuracy_without_shots() {
+        let mut stats = Stats::default();
+        assert_eq!(stats.accuracy(), 0);
+        stats.shots = 3;
+        stats.hits = 2;
+        assert_eq!(stats.accuracy(), 66);
+    }
+
+    #[test]
+    fn combine_adds_counts_and_keeps_the_longest_time() {
+        let mut a = Stats { shots: 10, bosses: 1, ..Default::default() };
+        a.add_kill(EnemyKind::Turret);
+        for _ in 0..5 {
+            a.add_frame(Cycle::Day);
+        }
+        let mut b = Stats { shots: 4, ..Default::default() };
+        b.add_kill(EnemyKind::Turret);
+        b.add_kill(EnemyKind::Blaster);
+        for _ in 0..3 {
+            b.add_frame(Cycle::Day);
+        }
+        for _ in 0..2 {
+            b.add_frame(Cycle::Night);
+        }
+
+        let both = a.combine(&b);
+        assert_eq!(both.shots, 14);
+        assert_eq!(both.kills(EnemyKind::Turret), 2);
+        assert_eq!(both.total_kills(), 4);
+        assert_eq!(both.frames(Cycle::Day), 5);
+        assert_eq!(both.frames(Cycle::Night), 2);
+    }
+}
@@ -1,4 +1,4 @@
-/// Some mathematical functions
+//! Some mathematical functions
 
 /// Returns if two ranges overlap.
 pub fn range_overlap(x1: i32, x2: i32, y1: i32, y2: i32) -> bool {
@@ -27,6 +27,20 @@ pub fn bit_range(value: u8, start: usize, end: usize) -> u8 {
     masked_val >> (8 - end)
 }
 
+/// Radians from straight down to face `to` from `from`, with positive angles
+/// towards the right.
+pub fn angle_to(from: (f32, f32), to: (f32, f32)) -> f32 {
+    (to.0 - from.0).atan2(to.1 - from.1)
+}
+
+/// Restricts `value` to between `min` and `max`.  Used instead of `f32::clamp`,
+/// whose debug assertion formats floats and so pulls the float printing code
+/// into builds that otherwise never print one.
+#[allow(clippy::manual_clamp)]
+pub fn clamp(value: f32, min: f32, max: f32) -> f32 {
+    value.max(min).min(max)
+}
+
 /// Find the value w proportion along the line from start to end
 pub fn interpolate(start: f32, end: f32, w: f32) -> f32 {
     (end - start) * (3.0 - w * 2.0) * w * w + start
@@ -36,7 +50,7 @@ pub fn interpolate(start: f32, end: f32, w: f32) -> f32 {
 /// Generates a random gradient with a seed fixed by the x, y coordinates
 pub fn random_gradient(x: i32, y: i32) -> (f32, f32) {
     let seed = x + 160 * y;
-    let mut r = Random::seed(seed.abs() as u32);
+    let mut r = Random::seed(seed.unsigned_abs());
     (r.angle().sin(), r.angle().cos())
 }
 
@@ -85,6 +99,16 @@ impl Random {
         r
     }
 
+    /// The raw internal state, which `from_state` turns back into an identical `Random`.
+    pub fn state(&self) -> u32 {
+        self.v
+    }
+
+    /// Continue from a state returned by `state`.
+    pub fn from_state(v: u32) -> Self {
+        Self { v }
+    }
+
     /// Perform an xor shift
     fn next(&mut self) {
         let mut v = self.v;
@@ -94,7 +118,8 @@ impl Random {
         self.v = v;
     }
 
-    fn float(&mut self) -> f32 {
+    /// A random float from 0 to 1.
+    pub fn float(&mut self) -> f32 {
         self.next();
         self.v as f32 / u32::MAX as f32
     }
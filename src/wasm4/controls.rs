@@ -1,14 +1,12 @@
-use crate::wasm4::sys::*;
+use crate::platform::*;
+use crate::replay::{Source, Recording, Playback};
 
 /// Returns the current mouse position
 pub fn mouse_position() -> (i16, i16) {
-    return unsafe {(
-        *MOUSE_X.as_ref().unwrap(), 
-        *MOUSE_Y.as_ref().unwrap()
-    )}
+    mouse()
 }
 
-/// The list of inputs for the first gamepad, and the mouse
+/// The list of inputs for a gamepad, and the mouse
 #[derive(Clone, Copy, Debug)]
 pub enum Button {
     Primary,
@@ -44,7 +42,7 @@ impl Button {
         !self.is_gamepad()
     }
 
-    /// Returns if the button is part of the first gamepad
+    /// Returns if the button is part of the gamepad
     pub fn is_gamepad(self) -> bool {
         use Button::*;
         !matches!(self, MouseLeft | MouseRight | MouseMiddle)
@@ -61,6 +59,12 @@ pub struct Controls {
     mouse_gamepad: u8,
     mouse_pressed: u8,
     mouse_held: u8,
+
+    source: Source,
+    /// Which gamepad is read, from 0
+    pad: usize,
+    /// Buttons read as up until they are let go, set by `ignore_held`
+    ignored: u8,
 }
 
 impl Controls {
@@ -69,21 +73,59 @@ impl Controls {
         Default::default()
     }
 
+    /// A `Controls` that records every gamepad state it reads
+    pub fn record(seed: u32) -> Self {
+        Self {
+            source: Source::Record(Recording::new(seed)),
+            ..Default::default()
+        }
+    }
+
+    /// A `Controls` that reads the gamepad state from a recording instead
+    pub fn replay(recording: Recording) -> Self {
+        Self {
+            source: Source::Replay(Playback::new(recording)),
+            ..Default::default()
+        }
+    }
+
+    /// Reads gamepad `idx` instead of the first one
+    pub fn on_gamepad(mut self, idx: usize) -> Self {
+        self.pad = idx;
+        self
+    }
+
+    /// Returns if a recording is being played back
+    pub fn replaying(&self) -> bool {
+        matches!(self.source, Source::Replay(_))
+    }
+
+    /// Goes back to reading the gamepad, returning the recording that was
+    /// being recorded or played back, if any
+    pub fn finish(&mut self) -> Option<Recording> {
+        self.source.finish()
+    }
+
+    /// Ignores whatever is held on the gamepad right now until it is let go,
+    /// so a press meant for something else doesn't carry over.  Runs
+    /// through any recording, so replays see the same thing
+    pub fn ignore_held(&mut self) {
+        self.ignored = gamepad(self.pad);
+    }
+
     /// Reads the current gamepad and mouse buttons state
     pub fn next(&mut self) {
-        let current = unsafe { *GAMEPAD1 };
-        let mouse_current = unsafe { *MOUSE_BUTTONS };
+        self.ignored &= gamepad(self.pad);
+        let current = self.source.read(self.pad, !self.ignored);
+        let mouse_current = mouse_buttons();
 
-        let next = Self {
-            gamepad: current,
-            pressed: current & (current ^ self.gamepad),
-            held: self.gamepad & current,
+        self.pressed = current & (current ^ self.gamepad);
+        self.held = self.gamepad & current;
+        self.gamepad = current;
 
-            mouse_gamepad: mouse_current,
-            mouse_pressed: mouse_current & (mouse_current ^ self.mouse_gamepad),
-            mouse_held: self.mouse_gamepad & mouse_current,
-        };
-        *self = next;
+        self.mouse_pressed = mouse_current & (mouse_current ^ self.mouse_gamepad);
+        self.mouse_held = self.mouse_gamepad & mouse_current;
+        self.mouse_gamepad = mouse_current;
     }
 
     /// Returns if a button is pressed this frame, but was not pressed last frame
@@ -108,3 +150,29 @@ impl Controls {
         self.held(but) || self.pressed(but)
     }
 }
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use crate::platform::headless;
+
+    fn hold(controls: &mut Controls, gamepad: u8) {
+        headless::with(|h| h.gamepads[0] = gamepad);
+        controls.next();
+    }
+
+    #[test]
+    fn ignored_buttons_wait_to_be_let_go() {
+        let mut controls = Controls::new();
+        hold(&mut controls, BUTTON_1);
+        controls.ignore_held();
+
+        hold(&mut controls, BUTTON_1 | BUTTON_LEFT);
+        assert!(!controls.pressed_or_held(Button::Primary));
+        assert!(controls.pressed(Button::Left));
+
+        hold(&mut controls, 0);
+        hold(&mut controls, BUTTON_1);
+        assert!(controls.pressed(Button::Primary));
+    }
+}
@@ -21,7 +21,7 @@ macro_rules! main {
         #[no_mangle]
         unsafe extern "C" fn update() {
             // SAFETY: WASM-4 is single-threaded. `update()` function is called after start by WASM-4 runtime
-            let rt = RUNTIME.assume_init_mut();
+            let rt = (*core::ptr::addr_of_mut!(RUNTIME)).assume_init_mut();
             <$runtime as $crate::wasm4::runtime::Runtime>::update(rt);
         }
     };
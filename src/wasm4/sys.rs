@@ -1,7 +1,12 @@
 //
 // WASM-4: https://wasm4.org/docs
+//
+// The functions in here are imported from the WASM-4 runtime, so they only
+// exist when building for wasm32.  Everything else should go through
+// `crate::platform` instead.
 
 #![allow(unused)]
+#![allow(clippy::manual_dangling_ptr)]
 
 // ┌───────────────────────────────────────────────────────────────────────────┐
 // │                                                                           │
@@ -50,9 +55,11 @@ pub const SYSTEM_HIDE_GAMEPAD_OVERLAY: u8 = 2;
 // └───────────────────────────────────────────────────────────────────────────┘
 
 /// Copies pixels to the framebuffer.
+#[cfg(target_arch = "wasm32")]
 pub fn blit(sprite: &[u8], x: i32, y: i32, width: u32, height: u32, flags: u32) {
     unsafe { extern_blit(sprite.as_ptr(), x, y, width, height, flags) }
 }
+#[cfg(target_arch = "wasm32")]
 extern "C" {
     #[link_name = "blit"]
     fn extern_blit(sprite: *const u8, x: i32, y: i32, width: u32, height: u32, flags: u32);
@@ -60,6 +67,7 @@ extern "C" {
 
 /// Copies a subregion within a larger sprite atlas to the framebuffer.
 #[allow(clippy::too_many_arguments)]
+#[cfg(target_arch = "wasm32")]
 pub fn blit_sub(
     sprite: &[u8],
     x: i32,
@@ -85,6 +93,7 @@ pub fn blit_sub(
         )
     }
 }
+#[cfg(target_arch = "wasm32")]
 extern "C" {
     #[link_name = "blitSub"]
     fn extern_blit_sub(
@@ -107,61 +116,73 @@ pub const BLIT_FLIP_Y: u32 = 4;
 pub const BLIT_ROTATE: u32 = 8;
 
 /// Draws a line between two points.
+#[cfg(target_arch = "wasm32")]
 pub fn line(x1: i32, y1: i32, x2: i32, y2: i32) {
     unsafe { extern_line(x1, y1, x2, y2) }
 }
+#[cfg(target_arch = "wasm32")]
 extern "C" {
     #[link_name = "line"]
     fn extern_line(x1: i32, y1: i32, x2: i32, y2: i32);
 }
 
 /// Draws an oval (or circle).
+#[cfg(target_arch = "wasm32")]
 pub fn oval(x: i32, y: i32, width: u32, height: u32) {
     unsafe { extern_oval(x, y, width, height) }
 }
+#[cfg(target_arch = "wasm32")]
 extern "C" {
     #[link_name = "oval"]
     fn extern_oval(x: i32, y: i32, width: u32, height: u32);
 }
 
 /// Draws a rectangle.
+#[cfg(target_arch = "wasm32")]
 pub fn rect(x: i32, y: i32, width: u32, height: u32) {
     unsafe { extern_rect(x, y, width, height) }
 }
+#[cfg(target_arch = "wasm32")]
 extern "C" {
     #[link_name = "rect"]
     fn extern_rect(x: i32, y: i32, width: u32, height: u32);
 }
 
 /// Draws text using the built-in system font.
+#[cfg(target_arch = "wasm32")]
 pub fn text<T: AsRef<str>>(text: T, x: i32, y: i32) {
     let text_ref = text.as_ref();
     unsafe { extern_text(text_ref.as_ptr(), text_ref.len(), x, y) }
 }
+#[cfg(target_arch = "wasm32")]
 extern "C" {
     #[link_name = "textUtf8"]
     fn extern_text(text: *const u8, length: usize, x: i32, y: i32);
 }
 
 /// Draws a vertical line
+#[cfg(target_arch = "wasm32")]
 pub fn vline(x: i32, y: i32, len: u32) {
     unsafe {
         extern_vline(x, y, len);
     }
 }
 
+#[cfg(target_arch = "wasm32")]
 extern "C" {
     #[link_name = "vline"]
     fn extern_vline(x: i32, y: i32, len: u32);
 }
 
 /// Draws a horizontal line
+#[cfg(target_arch = "wasm32")]
 pub fn hline(x: i32, y: i32, len: u32) {
     unsafe {
         extern_hline(x, y, len);
     }
 }
 
+#[cfg(target_arch = "wasm32")]
 extern "C" {
     #[link_name = "hline"]
     fn extern_hline(x: i32, y: i32, len: u32);
@@ -174,9 +195,11 @@ extern "C" {
 // └───────────────────────────────────────────────────────────────────────────┘
 
 /// Plays a sound tone.
+#[cfg(target_arch = "wasm32")]
 pub fn tone(frequency: u32, duration: u32, volume: u32, flags: u32) {
     unsafe { extern_tone(frequency, duration, volume, flags) }
 }
+#[cfg(target_arch = "wasm32")]
 extern "C" {
     #[link_name = "tone"]
     fn extern_tone(frequency: u32, duration: u32, volume: u32, flags: u32);
@@ -197,6 +220,7 @@ pub const TONE_MODE4: u32 = 12;
 // │                                                                           │
 // └───────────────────────────────────────────────────────────────────────────┘
 
+#[cfg(target_arch = "wasm32")]
 extern "C" {
     /// Reads up to `size` bytes from persistent storage into the pointer `dest`.
     pub fn diskr(dest: *mut u8, size: u32) -> u32;
@@ -212,10 +236,12 @@ extern "C" {
 // └───────────────────────────────────────────────────────────────────────────┘
 
 /// Prints a message to the debug console.
+#[cfg(target_arch = "wasm32")]
 pub fn trace<T: AsRef<str>>(text: T) {
     let text_ref = text.as_ref();
     unsafe { extern_trace(text_ref.as_ptr(), text_ref.len()) }
 }
+#[cfg(target_arch = "wasm32")]
 extern "C" {
     #[link_name = "traceUtf8"]
     fn extern_trace(trace: *const u8, length: usize);
@@ -0,0 +1,174 @@
+//! What spawns, where and when.  Enemies come from two places: the random
+//! spawner, which picks from every `Spawn::Random` entry active this round and
+//! cycle whenever its cooldown runs out, and set pieces, which go off at a
+//! fixed frame into the cycle.  Everything here is data, so waves can be
+//! tuned without touching the game loop.
+
+use crate::{Cycle, EnemyKind, Random};
+
+/// Frames into the night before the boss shows up.  The night doesn't end
+/// until it is beaten.
+pub const BOSS_FRAME: u32 = 600;
+
+/// The last round, for entries that never stop.
+pub const FOREVER: u32 = u32::MAX;
+
+/// How an entry spawns its enemy.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Spawn {
+    /// Picked by the random spawner with odds `weight` out of the total weight
+    /// of everything active, as long as fewer than `cap` are on screen.
+    Random { weight: u32, cap: usize },
+    /// Spawned `frame` frames after the cycle starts, counting its transition,
+    /// at `x` or wherever the enemy usually spawns.
+    At { frame: u32, x: Option<f32> },
+}
+
+/// One line of the wave table.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Wave {
+    pub enemy: EnemyKind,
+    /// The cycle this entry is active in, or both.
+    pub cycle: Option<Cycle>,
+    /// First and last round this entry is active in.
+    pub rounds: (u32, u32),
+    pub spawn: Spawn,
+}
+
+/// Frames between random spawns.  Starts at `start` and drops by one every
+/// `step` frames down to `min`, and nights spawn `night` times as often.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Cooldown {
+    pub start: i32,
+    pub step: u32,
+    pub min: i32,
+    /// Numerator and denominator.
+    pub night: (i32, i32),
+}
+
+impl Cooldown {
+    /// The cooldown after a spawn `frame` frames into the run.
+    pub fn frames(&self, frame: u32, cycle: Cycle) -> i32 {
+        let cooldown = (self.start - (frame / self.step) as i32).max(self.min);
+        match cycle {
+            Cycle::Day => cooldown,
+            Cycle::Night => cooldown / self.night.1 * self.night.0,
+        }
+    }
+}
+
+const fn random(enemy: EnemyKind, cycle: Option<Cycle>, rounds: (u32, u32), weight: u32, cap: usize) -> Wave {
+    Wave { enemy, cycle, rounds, spawn: Spawn::Random { weight, cap } }
+}
+
+const fn at(enemy: EnemyKind, cycle: Option<Cycle>, rounds: (u32, u32), frame: u32, x: Option<f32>) -> Wave {
+    Wave { enemy, cycle, rounds, spawn: Spawn::At { frame, x } }
+}
+
+use EnemyKind::*;
+use Cycle::*;
+
+pub static WAVES: &[Wave] = &[
+    // blasters fill in everything else
+    random(Blaster, None, (1, FOREVER), 16, usize::MAX),
+
+    // turrets get more common every couple of rounds
+    random(Turret, None, (1, 2), 2, 3),
+    random(Turret, None, (3, 4), 3, 3),
+    random(Turret, None, (5, 6), 5, 3),
+    random(Turret, None, (7, FOREVER), 8, 3),
+
+    // kites from the second day
+    random(Kite, None, (2, FOREVER), 3, 2),
+
+    // a pair of turrets to open the night
+    at(Turret, Some(Night), (2, FOREVER), 180, Some(40.0)),
+    at(Turret, Some(Night), (2, FOREVER), 180, Some(120.0)),
+
+    // a line of kites halfway through the day
+    at(Kite, Some(Day), (3, FOREVER), 1350, Some(50.0)),
+    at(Kite, Some(Day), (3, FOREVER), 1350, Some(80.0)),
+    at(Kite, Some(Day), (3, FOREVER), 1350, Some(110.0)),
+];
+
+impl Wave {
+    fn active(&self, cycle: Cycle, round: u32) -> bool {
+        self.cycle.is_none_or(|c| c == cycle)
+            && (self.rounds.0..=self.rounds.1).contains(&round)
+    }
+}
+
+/// Picks what the random spawner spawns next, if anything can.  `on_screen`
+/// is the number of enemies of a kind already on screen.
+pub fn pick(
+    cycle: Cycle,
+    round: u32,
+    on_screen: impl Fn(EnemyKind) -> usize,
+    random: &mut Random,
+) -> Option<EnemyKind> {
+    let choices: Vec<(EnemyKind, u32)> = WAVES
+        .iter()
+        .filter(|w| w.active(cycle, round))
+        .filter_map(|w| match w.spawn {
+            Spawn::Random { weight, cap } if on_screen(w.enemy) < cap => Some((w.enemy, weight)),
+            _ => None,
+        })
+        .collect();
+
+    let total: u32 = choices.iter().map(|(_, w)| w).sum();
+    if total == 0 {
+        return None;
+    }
+    let mut roll = random.in_range(0, total);
+    for (enemy, weight) in choices {
+        if roll < weight {
+            return Some(enemy);
+        }
+        roll -= weight;
+    }
+    None
+}
+
+/// Set pieces that go off `frame` frames into the cycle.
+pub fn set_pieces(cycle: Cycle, round: u32, frame: u32) -> impl Iterator<Item = (EnemyKind, Option<f32>)> {
+    WAVES
+        .iter()
+        .filter(move |w| w.active(cycle, round))
+        .filter_map(move |w| match w.spawn {
+            Spawn::At { frame: f, x } if f == frame => Some((w.enemy, x)),
+            _ => None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cooldown_drops_to_the_minimum() {
+        let cooldown = Cooldown { start: 100, step: 300, min: 30, night: (3, 4) };
+        assert_eq!(cooldown.frames(0, Day), 100);
+        assert_eq!(cooldown.frames(3000, Day), 90);
+        assert_eq!(cooldown.frames(3000, Night), 66);
+        assert_eq!(cooldown.frames(FOREVER, Day), 30);
+    }
+
+    #[test]
+    fn picks_only_what_has_room() {
+        let mut random = Random::seed(1);
+        for _ in 0..100 {
+            let full = |enemy| if enemy == Blaster { usize::MAX } else { 0 };
+            assert_ne!(pick(Day, 1, full, &mut random), Some(Blaster));
+            assert_ne!(pick(Day, 1, |_| 0, &mut random), Some(Kite));
+        }
+        assert_eq!(pick(Day, 1, |_| usize::MAX, &mut random), None);
+    }
+
+    #[test]
+    fn set_pieces_go_off_on_their_frame() {
+        assert_eq!(set_pieces(Night, 1, 180).count(), 0);
+        assert_eq!(set_pieces(Night, 2, 180).count(), 2);
+        assert_eq!(set_pieces(Night, 2, 181).count(), 0);
+        assert!(set_pieces(Day, 3, 1350).all(|(enemy, x)| enemy == Kite && x.is_some()));
+    }
+}
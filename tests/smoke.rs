@@ -0,0 +1,133 @@
+//! Plays the game headless with scripted gamepads, in every mode and at every
+//! preset.  Nothing is checked along the way besides not panicking, so run
+//! this in both debug, for the overflow checks, and release.  Replays are
+//! checked to end the same way as the run they recorded.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use cart::platform::{self, headless};
+use cart::replay::Recording;
+use cart::*;
+
+const MODES: [Mode; 3] = [Mode::Solo, Mode::Shared, Mode::Separate];
+
+/// Frames played in each mode at each preset, enough to get through a few
+/// runs.  Drawing the clouds makes every frame slow, slower still in debug.
+const FRAMES: u32 = 600;
+
+/// Gamepad buttons that move and shoot, in any combination.
+const BUTTONS: [u8; 5] = [
+    platform::BUTTON_1,
+    platform::BUTTON_LEFT,
+    platform::BUTTON_RIGHT,
+    platform::BUTTON_UP,
+    platform::BUTTON_DOWN,
+];
+
+/// Holds random buttons on a gamepad for a random number of frames at a time,
+/// and now and then the back button on its own.
+struct Script {
+    random: Random,
+    gamepad: u8,
+    frames: u32,
+    /// Whether the back button is ever pressed, which pauses.
+    pause: bool,
+}
+
+impl Script {
+    fn new(seed: u32, pause: bool) -> Self {
+        Self { random: Random::seed(seed), gamepad: 0, frames: 0, pause }
+    }
+
+    fn next(&mut self) -> u8 {
+        if self.frames == 0 {
+            self.frames = self.random.in_range(1, 40);
+            self.gamepad = if self.pause && self.random.in_range(0, 20) == 0 {
+                platform::BUTTON_2
+            } else {
+                BUTTONS
+                    .iter()
+                    .filter(|_| self.random.in_range(0, 3) == 0)
+                    .fold(0, |gamepad, b| gamepad | b)
+            };
+        }
+        self.frames -= 1;
+        self.gamepad
+    }
+}
+
+/// Runs one frame with `gamepads` held, returning what the game asked for.
+fn tick(game: &mut Game, input: &mut Controls, gamepads: [u8; 2]) -> Option<GameAction> {
+    headless::with(|h| {
+        h.next_frame();
+        h.gamepads[..2].copy_from_slice(&gamepads);
+    });
+    input.next();
+    game.tick(input)
+}
+
+/// Text drawn on the last frame.
+fn texts() -> Vec<String> {
+    headless::with(|h| h.texts.iter().map(|(text, _, _)| text.clone()).collect())
+}
+
+/// The last `count` recordings written to the console.
+fn traced(count: usize) -> Vec<Recording> {
+    headless::with(|h| {
+        let hex: Vec<String> = h.traces.split(|t| t == "Recording:").map(|lines| lines.concat()).collect();
+        hex[hex.len() - count..].iter().map(|hex| Recording::from_hex(hex).unwrap()).collect()
+    })
+}
+
+/// Every preset, with the custom one mixing levels.
+fn difficulties() -> impl Iterator<Item = Difficulty> {
+    let custom = Custom { health: Level::Easy, spawns: Level::Hard, drops: Level::Normal, cycle: Level::Hard };
+    Preset::ALL.into_iter().map(move |preset| Difficulty::new(preset, custom))
+}
+
+#[test]
+fn plays_every_mode_and_preset() {
+    for mode in MODES {
+        for (seed, difficulty) in difficulties().enumerate() {
+            headless::with(|h| h.reset());
+            // a seed of 0 never changes
+            let seed = seed as u32 + 1;
+            let mut game = Game::new(Random::seed(seed), mode, difficulty);
+            let mut input = Controls::new();
+            let mut scripts = [Script::new(seed, true), Script::new(seed + 100, false)];
+
+            for _ in 0..FRAMES {
+                let gamepads = [scripts[0].next(), scripts[1].next()];
+                if let Some(GameAction::Quit) = tick(&mut game, &mut input, gamepads) {
+                    game = Game::new(Random::seed(seed), mode, difficulty);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn replays_end_the_same_way() {
+    for mode in [Mode::Solo, Mode::Separate] {
+        headless::with(|h| h.reset());
+        let difficulty = Difficulty::default();
+        let mut game = Game::new(Random::seed(7), mode, difficulty);
+        let mut input = Controls::new();
+        let mut scripts = [Script::new(7, false), Script::new(8, false)];
+        while game.recordings().is_empty() {
+            tick(&mut game, &mut input, [scripts[0].next(), scripts[1].next()]);
+        }
+        tick(&mut game, &mut input, [0, 0]);
+        // replays never make the high score table
+        let mut ended = texts();
+        ended.retain(|text| !text.starts_with("New high score"));
+
+        // read back from the console, the way a bug report would be
+        let mut replay = Game::replay(mode, difficulty, traced(game.recordings().len()));
+        while replay.recordings().is_empty() {
+            tick(&mut replay, &mut input, [0, 0]);
+        }
+        tick(&mut replay, &mut input, [0, 0]);
+        assert_eq!(texts(), ended, "{mode:?}");
+    }
+}